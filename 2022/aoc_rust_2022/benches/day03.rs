@@ -0,0 +1,20 @@
+//! Benchmarks of 2022: Advent of Code day 3, bitmask against hash set item sets
+//! By Peter Fornwall
+
+use aoc_rust_2022::day03::{parse, part1, part1_hashset, part2, part2_hashset};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn bench_item_sets(c: &mut Criterion) {
+    let input = include_str!("../../inputs/day03_input.txt");
+    let v = parse(input).unwrap();
+
+    let mut group = c.benchmark_group("day03");
+    group.bench_function("part1 bitmask", |b| b.iter(|| part1(&v)));
+    group.bench_function("part1 hashset", |b| b.iter(|| part1_hashset(&v)));
+    group.bench_function("part2 bitmask", |b| b.iter(|| part2(&v)));
+    group.bench_function("part2 hashset", |b| b.iter(|| part2_hashset(&v)));
+    group.finish();
+}
+
+criterion_group!(benches, bench_item_sets);
+criterion_main!(benches);
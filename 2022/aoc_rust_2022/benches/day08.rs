@@ -0,0 +1,47 @@
+//! Benchmarks of 2022: Advent of Code day 8, monotonic stacks against walking out from every tree
+//! By Peter Fornwall
+
+use aoc_rust_2022::day08::{
+    generate_forest, parse, part1, part1_reference, part2, part2_reference,
+};
+use criterion::{criterion_group, criterion_main, Criterion};
+use rand::{rngs::StdRng, SeedableRng};
+
+/// Trees growing taller towards the middle, so every tree sees all the way
+/// to the edge. Heights are not single digits, so this can't be an input.
+fn pyramid(size: usize) -> Vec<Vec<i32>> {
+    (0..size)
+        .map(|y| {
+            (0..size)
+                .map(|x| x.min(y).min(size - 1 - x).min(size - 1 - y) as i32)
+                .collect()
+        })
+        .collect()
+}
+
+fn bench_forest(c: &mut Criterion) {
+    let forests = [
+        (
+            "random",
+            parse(&generate_forest(&mut StdRng::seed_from_u64(0), 1000, 1000)),
+        ),
+        ("pyramid", pyramid(1000)),
+    ];
+
+    let mut group = c.benchmark_group("day08 1000x1000");
+    group.sample_size(10);
+    for (name, v) in &forests {
+        group.bench_function(format!("part1 stack {name}"), |b| b.iter(|| part1(v)));
+        group.bench_function(format!("part1 reference {name}"), |b| {
+            b.iter(|| part1_reference(v))
+        });
+        group.bench_function(format!("part2 stack {name}"), |b| b.iter(|| part2(v)));
+        group.bench_function(format!("part2 reference {name}"), |b| {
+            b.iter(|| part2_reference(v))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_forest);
+criterion_main!(benches);
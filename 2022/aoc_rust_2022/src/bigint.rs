@@ -0,0 +1,237 @@
+//! Arbitrary precision integers for the solutions that need exact big numbers
+//! By Peter Fornwall
+
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, Mul, Neg, Sub},
+};
+
+/// Signed integer of any size, as a sign and little endian 32 bit limbs.
+/// Slow compared to the machine integers, but it never overflows.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BigInt {
+    negative: bool,
+    /// Without trailing zero limbs, so zero has no limbs and is never negative
+    limbs: Vec<u32>,
+}
+
+impl BigInt {
+    fn new(negative: bool, mut limbs: Vec<u32>) -> BigInt {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        BigInt {
+            negative: negative && !limbs.is_empty(),
+            limbs,
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    fn cmp_magnitude(a: &[u32], b: &[u32]) -> Ordering {
+        a.len()
+            .cmp(&b.len())
+            .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+    }
+
+    fn add_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+        let mut sum = Vec::with_capacity(a.len().max(b.len()) + 1);
+        let mut carry = 0;
+        for i in 0..a.len().max(b.len()) {
+            let s = *a.get(i).unwrap_or(&0) as u64 + *b.get(i).unwrap_or(&0) as u64 + carry;
+            sum.push(s as u32);
+            carry = s >> 32;
+        }
+        sum.push(carry as u32);
+        sum
+    }
+
+    /// `a - b` where `a` is at least as large as `b`
+    fn sub_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+        let mut diff = Vec::with_capacity(a.len());
+        let mut borrow = 0;
+        for (i, &limb) in a.iter().enumerate() {
+            let d = limb as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+            diff.push(d.rem_euclid(1 << 32) as u32);
+            borrow = (d < 0) as i64;
+        }
+        diff
+    }
+
+    fn add_signed(&self, other_negative: bool, other: &[u32]) -> BigInt {
+        if self.negative == other_negative {
+            return BigInt::new(self.negative, BigInt::add_magnitude(&self.limbs, other));
+        }
+        match BigInt::cmp_magnitude(&self.limbs, other) {
+            Ordering::Less => {
+                BigInt::new(other_negative, BigInt::sub_magnitude(other, &self.limbs))
+            }
+            _ => BigInt::new(self.negative, BigInt::sub_magnitude(&self.limbs, other)),
+        }
+    }
+
+    /// Magnitude divided by `d`, with the remainder
+    fn divrem_magnitude(&self, d: u64) -> (Vec<u32>, u64) {
+        let mut quotient = vec![0; self.limbs.len()];
+        let mut rem: u128 = 0;
+        for (i, &limb) in self.limbs.iter().enumerate().rev() {
+            let cur = (rem << 32) | limb as u128;
+            quotient[i] = (cur / d as u128) as u32;
+            rem = cur % d as u128;
+        }
+        (quotient, rem as u64)
+    }
+
+    /// Division rounding towards zero, as for `i64`. Panics if `d` is 0.
+    pub fn div_i64(&self, d: i64) -> BigInt {
+        assert!(d != 0, "division by zero");
+        let (quotient, _) = self.divrem_magnitude(d.unsigned_abs());
+        BigInt::new(self.negative != (d < 0), quotient)
+    }
+
+    /// Non-negative remainder, as `i64::rem_euclid`. Panics if `d` is 0.
+    pub fn rem_i64(&self, d: i64) -> i64 {
+        assert!(d != 0, "division by zero");
+        let (_, rem) = self.divrem_magnitude(d.unsigned_abs());
+        let rem = if self.negative {
+            -(rem as i128)
+        } else {
+            rem as i128
+        };
+        rem.rem_euclid(d as i128) as i64
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(value: i64) -> Self {
+        let magnitude = value.unsigned_abs();
+        BigInt::new(value < 0, vec![magnitude as u32, (magnitude >> 32) as u32])
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::new(!self.negative, self.limbs.clone())
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: &BigInt) -> BigInt {
+        self.add_signed(other.negative, &other.limbs)
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: &BigInt) -> BigInt {
+        self.add_signed(!other.negative, &other.limbs)
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, other: &BigInt) -> BigInt {
+        let mut product = vec![0u32; self.limbs.len() + other.limbs.len()];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0;
+            for (j, &b) in other.limbs.iter().enumerate() {
+                let p = a as u64 * b as u64 + product[i + j] as u64 + carry;
+                product[i + j] = p as u32;
+                carry = p >> 32;
+            }
+            product[i + other.limbs.len()] = carry as u32;
+        }
+        BigInt::new(self.negative != other.negative, product)
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut chunks = Vec::new();
+        let mut rest = BigInt::new(false, self.limbs.clone());
+        loop {
+            let (quotient, rem) = rest.divrem_magnitude(1_000_000_000);
+            rest = BigInt::new(false, quotient);
+            chunks.push(rem);
+            if rest.is_zero() {
+                break;
+            }
+        }
+        if self.negative {
+            write!(f, "-")?;
+        }
+        write!(f, "{}", chunks.pop().unwrap())?;
+        for chunk in chunks.iter().rev() {
+            write!(f, "{chunk:09}")?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_arithmetic() {
+    let big = BigInt::from;
+    let limb = big(1 << 32);
+
+    // Carry into a new limb, and a borrow through all the limbs back down
+    let max = big(u32::MAX as i64);
+    assert_eq!(&max + &big(1), limb);
+    let two_limbs = &(&limb * &limb) - &big(1);
+    assert_eq!(two_limbs.to_string(), u64::MAX.to_string());
+    assert_eq!(&(&two_limbs + &big(1)) - &big(1), two_limbs);
+    assert_eq!(&two_limbs - &two_limbs, BigInt::default());
+
+    // Signs, and zero is never negative
+    assert_eq!(&big(-7) + &big(3), big(-4));
+    assert_eq!(&big(3) - &big(7), big(-4));
+    assert_eq!(&big(-3) - &big(-7), big(4));
+    assert_eq!(&big(-3) * &big(-7), big(21));
+    assert_eq!(&big(-3) * &big(0), big(0));
+    assert!(!(&big(-3) + &big(3)).is_negative());
+    assert_eq!(-&big(0), big(0));
+    assert_eq!(big(i64::MIN).to_string(), i64::MIN.to_string());
+
+    let product = &big(i64::MAX) * &big(i64::MIN);
+    let expected = i64::MAX as i128 * i64::MIN as i128;
+    assert_eq!(product.to_string(), expected.to_string());
+    assert_eq!(
+        (&product * &product).to_string(),
+        "7237005577332262212403911129196324049955497277530853264306353791236504027136"
+    );
+}
+
+#[test]
+fn test_division() {
+    let big = BigInt::from;
+    // Rounding towards zero, remainders never negative
+    assert_eq!(big(-7).div_i64(2), big(-3));
+    assert_eq!(big(7).div_i64(-2), big(-3));
+    assert_eq!(big(-7).div_i64(-2), big(3));
+    assert_eq!(big(-7).rem_i64(3), 2);
+    assert_eq!(big(7).rem_i64(-3), 1);
+    assert_eq!(big(0).rem_i64(3), 0);
+
+    // Divisors above 32 bits carry remainders across limbs
+    let product = &big(i64::MAX) * &big(i64::MIN);
+    let expected = i64::MAX as i128 * i64::MIN as i128;
+    for d in [3, 1_000_000_007, i64::MAX, i64::MIN] {
+        assert_eq!(product.rem_i64(d), expected.rem_euclid(d as i128) as i64);
+        assert_eq!(
+            product.div_i64(d).to_string(),
+            (expected / d as i128).to_string()
+        );
+    }
+}
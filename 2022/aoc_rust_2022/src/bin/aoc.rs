@@ -0,0 +1,226 @@
+//! Runner for the 2022: Advent of Code solutions
+//! By Peter Fornwall
+
+use std::{
+    fs::File,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::Instant,
+};
+
+use aoc_rust_2022::{
+    sniff::Confidence,
+    solutions::{self, Day},
+};
+use clap::{Parser, Subcommand};
+use rand::{rngs::StdRng, SeedableRng};
+use tracing_subscriber::{
+    filter::LevelFilter, fmt::format::FmtSpan, layer::SubscriberExt, util::SubscriberInitExt, Layer,
+};
+
+#[derive(Parser)]
+#[command(about = "Run Advent of Code 2022 solutions")]
+struct Cli {
+    /// More diagnostics on stderr (-v info, -vv debug, -vvv trace)
+    #[arg(short, long, global = true, action = clap::ArgAction::Count, conflicts_with = "quiet")]
+    verbose: u8,
+    /// No diagnostics on stderr, only the answers
+    #[arg(short, long, global = true)]
+    quiet: bool,
+    /// Also write all trace events and span timings as JSON lines to this file
+    #[arg(long, global = true, value_name = "FILE")]
+    trace_json: Option<PathBuf>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve one day, or all days if no day is given
+    Run {
+        day: Option<u8>,
+        /// Puzzle input to use instead of the checked in one
+        #[arg(long, requires = "day")]
+        input: Option<PathBuf>,
+    },
+    /// Guess which day an input file belongs to
+    Identify { file: PathBuf },
+    /// Run all implementations of each part and report any disagreement
+    Crosscheck {
+        /// Only check this day, otherwise all days with several implementations
+        day: Option<u8>,
+        /// Check this input instead of generated ones
+        #[arg(long, requires = "day")]
+        input: Option<PathBuf>,
+        /// Number of generated inputs per day
+        #[arg(long, default_value_t = 100)]
+        cases: u64,
+        /// Seed of the first generated input
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+}
+
+fn init_tracing(cli: &Cli) -> std::io::Result<()> {
+    let level = match (cli.quiet, cli.verbose) {
+        (true, _) => LevelFilter::OFF,
+        (_, 0) => LevelFilter::WARN,
+        (_, 1) => LevelFilter::INFO,
+        (_, 2) => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    };
+    let stderr_layer = tracing_subscriber::fmt::layer()
+        .with_writer(std::io::stderr)
+        .with_span_events(if level >= LevelFilter::DEBUG {
+            FmtSpan::CLOSE
+        } else {
+            FmtSpan::NONE
+        })
+        .with_filter(level);
+    let json_layer = match &cli.trace_json {
+        Some(path) => Some(
+            tracing_subscriber::fmt::layer()
+                .json()
+                .with_span_events(FmtSpan::CLOSE)
+                .with_writer(Mutex::new(File::create(path)?))
+                .with_filter(level.max(LevelFilter::DEBUG)),
+        ),
+        None => None,
+    };
+    tracing_subscriber::registry()
+        .with(stderr_layer)
+        .with(json_layer)
+        .init();
+    Ok(())
+}
+
+fn read_input(path: &Path) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))
+}
+
+fn select_days(day: Option<u8>) -> Result<Vec<Day>, String> {
+    match day {
+        Some(nr) => Ok(vec![
+            solutions::find(nr).ok_or(format!("no solution for day {nr}"))?
+        ]),
+        None => Ok(solutions::days()),
+    }
+}
+
+fn check_input(day: &Day, input: &str) {
+    if (day.looks_like)(input) != Confidence::No {
+        return;
+    }
+    match solutions::identify(input).first() {
+        Some((guess, _)) => tracing::warn!(
+            day = day.day,
+            guess,
+            "input does not look like day {}, it looks like day {guess}",
+            day.day
+        ),
+        None => tracing::warn!(day = day.day, "input does not look like day {}", day.day),
+    }
+}
+
+fn run_day(day: &Day, input: &str) {
+    for (part, impls) in (1..).zip([&day.part1, &day.part2]) {
+        let Some(solver) = impls.first() else {
+            continue;
+        };
+        let _span = tracing::info_span!("solve", day = day.day, part).entered();
+        let start = Instant::now();
+        let answer = (solver.solve)(input);
+        tracing::info!(
+            elapsed_ms = start.elapsed().as_secs_f64() * 1000.0,
+            "solved"
+        );
+        if answer.contains('\n') {
+            println!("Day {:02} Part{part}:\n{}", day.day, answer.trim_end());
+        } else {
+            println!("Day {:02} Part{part}: {answer}", day.day);
+        }
+    }
+}
+
+/// Cross-checks one input, printing any mismatches. Returns the number of mismatches.
+fn crosscheck_input(day: &Day, input: &str, source: &str, show_input: bool) -> usize {
+    let _span = tracing::info_span!("crosscheck", day = day.day, source).entered();
+    let mismatches = solutions::crosscheck(day, input);
+    for mismatch in &mismatches {
+        let failed = mismatch.answers.iter().any(|(_, answer)| answer.is_err());
+        let verb = if failed { "fails" } else { "disagrees" };
+        println!(
+            "Day {:02} Part{} {verb} on {source}:",
+            day.day, mismatch.part
+        );
+        for (name, answer) in &mismatch.answers {
+            match answer {
+                Ok(answer) => println!("  {name}: {answer}"),
+                Err(message) => println!("  {name}: panicked: {message}"),
+            }
+        }
+        if show_input {
+            println!("{input}");
+        }
+    }
+    mismatches.len()
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+    init_tracing(&cli)?;
+
+    match cli.command {
+        Command::Run { day, input } => {
+            for day in &select_days(day)? {
+                let path = input
+                    .clone()
+                    .unwrap_or_else(|| solutions::default_input(day.day));
+                let content = read_input(&path)?;
+                check_input(day, &content);
+                run_day(day, &content);
+            }
+        }
+        Command::Identify { file } => {
+            let content = read_input(&file)?;
+            let matches = solutions::identify(&content);
+            if matches.is_empty() {
+                return Err(format!("{}: no day matches this input", file.display()).into());
+            }
+            for (day, confidence) in matches {
+                println!("Day {day:02}: {confidence:?}");
+            }
+        }
+        Command::Crosscheck {
+            day,
+            input,
+            cases,
+            seed,
+        } => {
+            let mut failures = 0;
+            let days = select_days(day)?;
+            let days = days
+                .iter()
+                .filter(|d| d.part1.len() > 1 || d.part2.len() > 1);
+            for day in days {
+                if let Some(path) = &input {
+                    let content = read_input(path)?;
+                    failures += crosscheck_input(day, &content, &path.display().to_string(), false);
+                    println!("Day {:02}: checked {}", day.day, path.display());
+                } else if let Some(generate) = day.generate {
+                    for seed in seed..seed + cases {
+                        let content = generate(&mut StdRng::seed_from_u64(seed));
+                        failures += crosscheck_input(day, &content, &format!("seed {seed}"), true);
+                    }
+                    println!("Day {:02}: checked {cases} generated inputs", day.day);
+                } else {
+                    tracing::warn!(day = day.day, "no input generator, use --input");
+                }
+            }
+            if failures > 0 {
+                return Err(format!("{failures} disagreements found").into());
+            }
+        }
+    }
+    Ok(())
+}
@@ -1,32 +1,28 @@
 //! Solutions to 2022: Advent of Code day 1
 //! By Peter Fornwall
+//!
+//! Run with `<file> [k]` to stream the top k elves (default 3) from a file of any size.
 
-fn parse(content: &str) -> Vec<i64> {
-    content
-        .trim()
-        .split("\n\n")
-        .map(|elf| elf.lines().map(|x| x.trim().parse::<i64>().unwrap()).sum())
-        .collect::<Vec<_>>()
-}
-
-pub fn part1(elves: &[i64]) -> i64 {
-    *elves.iter().max().unwrap()
-}
-
-pub fn part2(elves: &[i64]) -> i64 {
-    let mut v = elves.to_vec();
-    v.sort_unstable();
-    v.iter().rev().take(3).sum()
-}
+use aoc_rust_2022::day01::{part1, part2, top_k};
+use std::{fs::File, io::BufReader};
 
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(path) = args.first() {
+        let k = args.get(1).map_or(3, |k| k.parse::<usize>().unwrap());
+        let reader = BufReader::new(File::open(path).unwrap());
+        for elf in top_k(reader, k).unwrap() {
+            println!("Elf {}: {}", elf.index, elf.calories);
+        }
+        return;
+    }
+
     let input = include_str!("../../../inputs/day01_input.txt");
-    let elves = parse(input);
 
-    let result_p1 = part1(&elves);
+    let result_p1 = part1(input);
     println!("Part1: {}", result_p1);
     assert_eq!(result_p1, 72240);
-    let result_p2 = part2(&elves);
+    let result_p2 = part2(input);
     println!("Part2: {}", result_p2);
     assert_eq!(result_p2, 210957);
 }
@@ -1,42 +1,7 @@
 //! Solutions to 2022: Advent of Code day 2
 //! By Peter Fornwall
 
-fn parse(content: &str) -> Vec<(i32, i32)> {
-    content
-        .lines()
-        .map(|line| {
-            (
-                line.as_bytes()[0] as i32 - 'A' as i32,
-                line.as_bytes()[2] as i32 - 'X' as i32,
-            )
-        })
-        .collect()
-}
-
-fn score(play1: i32, play2: i32) -> i32 {
-    let mut s = (play2 + 1) as i32;
-    if play1 == play2 {
-        s += 3;
-    } else if (play1 + 1) % 3 == play2 {
-        s += 6;
-    }
-    s
-}
-
-fn part1(values: &[(i32, i32)]) -> i32 {
-    values.iter().map(|(p1, p2)| score(*p1, *p2)).sum()
-}
-
-fn part2(values: &[(i32, i32)]) -> i32 {
-    values
-        .iter()
-        .map(|&(p1, result)| {
-            let r = result - 1;
-            let p2 = if r == 0 { p1 } else { (p1 + r).rem_euclid(3) };
-            score(p1, p2)
-        })
-        .sum()
-}
+use aoc_rust_2022::day02::{parse, part1, part2};
 
 fn main() {
     let input = include_str!("../../../inputs/day02_input.txt");
@@ -1,87 +1,48 @@
 //! Solutions to 2022: Advent of Code day 7
 //! By Peter Fornwall
+//!
+//! Run with `json <session>` or `script <session>` to export a terminal session,
+//! or with `transcript <dir>` to make a session exploring a real directory.
+//! Run with `plan <session> <disk size> <required>` to list what to delete.
 
-use std::collections::HashMap;
+use aoc_rust_2022::day07::{parse, part1, part2, FileSystem};
+use std::path::Path;
 
-fn parse(input: &str) -> HashMap<String, i32> {
-    let mut m: HashMap<String, i32> = HashMap::new();
-    let lines: Vec<_> = input.trim().lines().collect();
-    let mut stack_dirs = Vec::new();
-    let mut line_nr = 0;
-    while line_nr < lines.len() {
-        let line_parts: Vec<_> = lines[line_nr].split(' ').collect();
-        if line_parts[1] == "cd" {
-            if line_parts[2] == ".." {
-                let dir_path = stack_dirs.join("/");
-                let calc_size = *m.entry(dir_path).or_insert(0);
-                stack_dirs.pop();
-                let dir_path = stack_dirs.join("/");
-                m.entry(dir_path)
-                    .and_modify(|x| {
-                        *x += calc_size;
-                    })
-                    .or_insert(calc_size);
-            } else {
-                stack_dirs.push(line_parts[2]);
-            }
-        } else if line_parts[1] == "ls" {
-            for (ls_line_nr, ls_line) in lines.iter().enumerate().skip(line_nr + 1) {
-                let ls_line_parts: Vec<_> = ls_line.split(' ').collect();
-                if ls_line_parts[0] == "$" {
-                    line_nr = ls_line_nr - 1;
-                    break;
-                } else if ls_line_parts[0] == "dir" {
-                    continue;
-                }
-                let dir_size = ls_line_parts[0].parse::<i32>().unwrap();
-                let dir_path = stack_dirs.join("/");
-                m.entry(dir_path.clone())
-                    .and_modify(|x| {
-                        *x += dir_size;
-                    })
-                    .or_insert(dir_size);
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let [command, path, disk_size, required] = &args[..] {
+        assert_eq!(command, "plan", "unknown command {command}");
+        let fs = parse(&std::fs::read_to_string(path).unwrap()).unwrap();
+        let (disk_size, required) = (disk_size.parse().unwrap(), required.parse().unwrap());
+        let set = fs
+            .plan_cleanup_set(disk_size, required, 100_000_000)
+            .unwrap_or_else(|limit| {
+                println!("Set search gave up, not necessarily the best set");
+                Some(limit.best)
+            });
+        let plans = [fs.plan_cleanup(disk_size, required), set];
+        for (name, plan) in ["Single:", "Set:"].iter().zip(plans) {
+            match plan {
+                Some(plan) => println!("{name} {} frees {}", plan.paths.join(" "), plan.freed),
+                None => println!("{name} not possible"),
             }
         }
-        line_nr += 1;
-    }
-
-    while stack_dirs.len() > 1 {
-        let dir_path = stack_dirs.join("/");
-        let calc_size = *m.entry(dir_path).or_insert(0);
-        stack_dirs.pop();
-        let dir_path = stack_dirs.join("/");
-        m.entry(dir_path)
-            .and_modify(|x| {
-                *x += calc_size;
-            })
-            .or_insert(calc_size);
+        return;
     }
-
-    m
-}
-
-fn part1(input: &str) -> i32 {
-    let m = parse(input);
-    let items: Vec<_> = m.iter().filter(|x| x.1 <= &100_000).collect();
-    items.iter().map(|x| x.1).sum()
-}
-
-fn part2(input: &str) -> i32 {
-    let m = parse(input);
-    let mut items: Vec<_> = m.iter().collect();
-    items.sort_by_key(|k| k.1);
-    let root_size = m["/"];
-    let left = 70_000_000 - root_size;
-    let to_delete = 30_000_000 - left;
-    for item in items {
-        if *item.1 >= to_delete {
-            return *item.1;
+    if let [command, path] = &args[..] {
+        let session = || parse(&std::fs::read_to_string(path).unwrap()).unwrap();
+        match command.as_str() {
+            "json" => println!("{}", session().to_json()),
+            "script" => print!("{}", session().to_shell_script()),
+            "transcript" => print!(
+                "{}",
+                FileSystem::from_dir(Path::new(path)).unwrap().transcript()
+            ),
+            _ => panic!("unknown command {command}, expected json, script or transcript"),
         }
+        return;
     }
-    0
-}
 
-fn main() {
     let input = include_str!("../../../inputs/day07_input.txt");
 
     let result_p1 = part1(input);
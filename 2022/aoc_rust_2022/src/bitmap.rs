@@ -0,0 +1,82 @@
+//! Two-colour pixel grid shared by the solutions that draw pictures
+//! By Peter Fornwall
+
+use std::fmt;
+
+/// Grid of pixels that are either lit or dark, row by row from the top left.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bitmap {
+    width: usize,
+    height: usize,
+    pixels: Vec<bool>,
+}
+
+impl Bitmap {
+    /// All dark bitmap.
+    pub fn new(width: usize, height: usize) -> Self {
+        Bitmap {
+            width,
+            height,
+            pixels: vec![false; width * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        self.pixels[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, lit: bool) {
+        self.pixels[y * self.width + x] = lit;
+    }
+
+    /// One line per row, with `lit` and `dark` characters.
+    pub fn to_text(&self, lit: char, dark: char) -> String {
+        let mut s = String::with_capacity((self.width + 1) * self.height);
+        for row in self.pixels.chunks(self.width.max(1)) {
+            s.extend(row.iter().map(|p| if *p { lit } else { dark }));
+            s.push('\n');
+        }
+        s
+    }
+
+    /// Binary PBM image, lit pixels black.
+    pub fn to_pbm(&self) -> Vec<u8> {
+        let mut data = format!("P4\n{} {}\n", self.width, self.height).into_bytes();
+        for row in self.pixels.chunks(self.width.max(1)) {
+            for byte in row.chunks(8) {
+                data.push(
+                    byte.iter()
+                        .enumerate()
+                        .filter(|(_, p)| **p)
+                        .fold(0, |b, (i, _)| b | 0x80 >> i),
+                );
+            }
+        }
+        data
+    }
+}
+
+/// Lit pixels as `#` and dark as `.`, like the puzzle descriptions.
+impl fmt::Display for Bitmap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.to_text('#', '.'))
+    }
+}
+
+#[test]
+fn test_bitmap() {
+    let mut bitmap = Bitmap::new(10, 2);
+    bitmap.set(0, 0, true);
+    bitmap.set(9, 1, true);
+    assert!(bitmap.get(9, 1));
+    assert_eq!(bitmap.to_string(), "#.........\n.........#\n");
+    assert_eq!(bitmap.to_pbm(), b"P4\n10 2\n\x80\x00\x00\x40");
+}
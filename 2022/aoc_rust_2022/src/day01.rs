@@ -0,0 +1,126 @@
+//! Solutions to 2022: Advent of Code day 1
+//! By Peter Fornwall
+
+use crate::sniff::{self, Confidence};
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    io::{self, BufRead},
+};
+
+pub fn looks_like(input: &str) -> Confidence {
+    if !sniff::all_lines(input, |l| l.is_empty() || sniff::is_digits(l)) {
+        Confidence::No
+    } else if input.trim().contains("\n\n") {
+        Confidence::High
+    } else {
+        Confidence::Low
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elf {
+    pub calories: i64,
+    /// Position of the elf in the input, starting at 0
+    pub index: usize,
+}
+
+/// The `k` elves carrying the most calories, most first (earliest elf first on ties).
+/// Reads one line at a time and only keeps the current top `k` in memory.
+#[tracing::instrument(level = "debug", skip(reader))]
+pub fn top_k<R: BufRead>(reader: R, k: usize) -> io::Result<Vec<Elf>> {
+    // Min-heap on (calories, earliest elf), so the least wanted elf is popped first
+    let mut heap = BinaryHeap::with_capacity(k + 1);
+    let mut push = |calories: i64, index: usize| {
+        heap.push(Reverse((calories, Reverse(index))));
+        if heap.len() > k {
+            heap.pop();
+        }
+    };
+    let mut nr_elves = 0;
+    let mut current: Option<i64> = None;
+    for (line_nr, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() {
+            if let Some(calories) = current.take() {
+                push(calories, nr_elves);
+                nr_elves += 1;
+            }
+            continue;
+        }
+        let calories = line.parse::<i64>().map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line {}: {line:?}: {e}", line_nr + 1),
+            )
+        })?;
+        let total = current.unwrap_or(0).checked_add(calories).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line {}: calories of elf {nr_elves} overflow", line_nr + 1),
+            )
+        })?;
+        current = Some(total);
+    }
+    if let Some(calories) = current {
+        push(calories, nr_elves);
+        nr_elves += 1;
+    }
+    tracing::debug!(nr_elves, "elves counted");
+    Ok(heap
+        .into_sorted_vec()
+        .into_iter()
+        .map(|Reverse((calories, Reverse(index)))| Elf { calories, index })
+        .collect())
+}
+
+fn top_k_sum(input: &str, k: usize) -> io::Result<i64> {
+    top_k(input.as_bytes(), k)?
+        .iter()
+        .try_fold(0i64, |sum, elf| sum.checked_add(elf.calories))
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "sum of calories overflows"))
+}
+
+pub fn part1(input: &str) -> i64 {
+    top_k_sum(input, 1).unwrap()
+}
+
+pub fn part2(input: &str) -> i64 {
+    top_k_sum(input, 3).unwrap()
+}
+
+#[test]
+fn test1() {
+    let input = "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000";
+
+    let result_p1 = part1(input);
+    println!("Part1: {}", result_p1);
+    assert_eq!(result_p1, 24000);
+    let result_p2 = part2(input);
+    println!("Part2: {}", result_p2);
+    assert_eq!(result_p2, 45000);
+
+    let top = top_k(input.as_bytes(), 3).unwrap();
+    let indices: Vec<_> = top.iter().map(|elf| elf.index).collect();
+    assert_eq!(indices, [3, 2, 4]);
+
+    let huge = format!("{}\n\n{}\n", i64::MAX, i64::MAX);
+    assert_eq!(top_k(huge.as_bytes(), 1).unwrap()[0].calories, i64::MAX);
+    assert!(top_k_sum(&huge, 2).is_err());
+    let overflow = format!("{}\n1\n", i64::MAX);
+    assert!(top_k(overflow.as_bytes(), 1).is_err());
+}
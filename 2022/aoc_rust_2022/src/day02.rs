@@ -0,0 +1,205 @@
+//! Solutions to 2022: Advent of Code day 2
+//! By Peter Fornwall
+
+use crate::sniff::{self, Confidence};
+use itertools::Itertools;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Lose,
+    Draw,
+    Win,
+}
+
+#[derive(Debug, Clone)]
+pub enum Response {
+    /// The second column is the move to play
+    Move(HashMap<u8, usize>),
+    /// The second column is how the round needs to end
+    Outcome(HashMap<u8, Outcome>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Goal {
+    Max,
+    Min,
+}
+
+/// A game of cyclic dominance, like rock-paper-scissors(-lizard-spock).
+#[derive(Debug, Clone)]
+pub struct Game {
+    /// Odd number of moves, each move beats the (nr_moves - 1) / 2 moves before it
+    pub nr_moves: usize,
+    pub move_scores: Vec<i32>,
+    /// Score for losing, drawing and winning a round
+    pub outcome_scores: [i32; 3],
+    pub opponent: HashMap<u8, usize>,
+    pub response: Response,
+}
+
+impl Game {
+    /// Game where move `i` is the `i`:th opponent symbol and scores `i + 1`,
+    /// and a round scores 0, 3 or 6 for a loss, draw or win.
+    pub fn new(opponent_symbols: &[u8], response: Response) -> Self {
+        let nr_moves = opponent_symbols.len();
+        assert!(
+            nr_moves % 2 == 1,
+            "cyclic dominance needs an odd number of moves"
+        );
+        Game {
+            nr_moves,
+            move_scores: (1..=nr_moves as i32).collect(),
+            outcome_scores: [0, 3, 6],
+            opponent: opponent_symbols.iter().copied().zip(0..).collect(),
+            response,
+        }
+    }
+
+    pub fn rock_paper_scissors(response: Response) -> Self {
+        Game::new(b"ABC", response)
+    }
+
+    pub fn outcome(&self, opponent: usize, me: usize) -> Outcome {
+        let diff = (me + self.nr_moves - opponent) % self.nr_moves;
+        if diff == 0 {
+            Outcome::Draw
+        } else if diff <= self.nr_moves / 2 {
+            Outcome::Win
+        } else {
+            Outcome::Lose
+        }
+    }
+
+    /// Move giving the wanted outcome, the closest one in the cycle if there are several.
+    pub fn move_for(&self, opponent: usize, outcome: Outcome) -> usize {
+        match outcome {
+            Outcome::Draw => opponent,
+            Outcome::Win => (opponent + 1) % self.nr_moves,
+            Outcome::Lose => (opponent + self.nr_moves - 1) % self.nr_moves,
+        }
+    }
+
+    pub fn score(&self, opponent: usize, me: usize) -> i32 {
+        self.move_scores[me] + self.outcome_scores[self.outcome(opponent, me) as usize]
+    }
+
+    /// Score of one round, or None if a symbol is unknown.
+    pub fn round_score(&self, (col1, col2): (u8, u8)) -> Option<i32> {
+        let opponent = *self.opponent.get(&col1)?;
+        let me = match &self.response {
+            Response::Move(moves) => *moves.get(&col2)?,
+            Response::Outcome(outcomes) => self.move_for(opponent, *outcomes.get(&col2)?),
+        };
+        Some(self.score(opponent, me))
+    }
+
+    pub fn total(&self, rounds: &[(u8, u8)]) -> Option<i32> {
+        rounds.iter().map(|round| self.round_score(*round)).sum()
+    }
+
+    /// Mapping of the second column symbols to moves giving the highest or lowest total,
+    /// or None if there are unknown opponent symbols or more symbols than moves.
+    pub fn best_mapping(
+        &self,
+        rounds: &[(u8, u8)],
+        goal: Goal,
+    ) -> Option<(HashMap<u8, usize>, i32)> {
+        let mut counts: HashMap<(usize, u8), i32> = HashMap::new();
+        for (col1, col2) in rounds {
+            *counts
+                .entry((*self.opponent.get(col1)?, *col2))
+                .or_default() += 1;
+        }
+        let symbols: Vec<u8> = rounds.iter().map(|r| r.1).unique().sorted().collect();
+        if symbols.len() > self.nr_moves {
+            return None;
+        }
+        let totals = (0..self.nr_moves).permutations(symbols.len()).map(|moves| {
+            let mapping: HashMap<u8, usize> = symbols.iter().copied().zip(moves).collect();
+            let total = counts
+                .iter()
+                .map(|((opponent, symbol), count)| count * self.score(*opponent, mapping[symbol]))
+                .sum::<i32>();
+            (mapping, total)
+        });
+        match goal {
+            Goal::Max => totals.max_by_key(|(_, total)| *total),
+            Goal::Min => totals.min_by_key(|(_, total)| *total),
+        }
+    }
+}
+
+pub fn looks_like(input: &str) -> Confidence {
+    sniff::all_lines(input, |l| {
+        matches!(l.as_bytes(), [b'A'..=b'C', b' ', b'X'..=b'Z'])
+    })
+    .into()
+}
+
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn parse(content: &str) -> Vec<(u8, u8)> {
+    content
+        .lines()
+        .map(|line| (line.as_bytes()[0], line.as_bytes()[2]))
+        .collect()
+}
+
+pub fn part1(values: &[(u8, u8)]) -> i32 {
+    let moves = HashMap::from([(b'X', 0), (b'Y', 1), (b'Z', 2)]);
+    let game = Game::rock_paper_scissors(Response::Move(moves));
+    game.total(values).unwrap()
+}
+
+pub fn part2(values: &[(u8, u8)]) -> i32 {
+    let outcomes = HashMap::from([
+        (b'X', Outcome::Lose),
+        (b'Y', Outcome::Draw),
+        (b'Z', Outcome::Win),
+    ]);
+    let game = Game::rock_paper_scissors(Response::Outcome(outcomes));
+    game.total(values).unwrap()
+}
+
+#[test]
+fn test1() {
+    let input = "A Y
+B X
+C Z";
+    let v = parse(input);
+
+    let result_p1 = part1(&v);
+    println!("Part1: {}", result_p1);
+    assert_eq!(result_p1, 15);
+    let result_p2 = part2(&v);
+    println!("Part2: {}", result_p2);
+    assert_eq!(result_p2, 12);
+
+    let game = Game::rock_paper_scissors(Response::Move(HashMap::new()));
+    let (mapping, total) = game.best_mapping(&v, Goal::Max).unwrap();
+    assert_eq!(total, 24);
+    let best_game = Game::rock_paper_scissors(Response::Move(mapping));
+    assert_eq!(best_game.total(&v), Some(24));
+    assert_eq!(game.best_mapping(&v, Goal::Min).unwrap().1, 6);
+}
+
+#[test]
+fn test_rock_paper_scissors_lizard_spock() {
+    // Rock, Spock, Paper, Lizard, Scissors: each beats the two before it
+    let game = Game::new(b"RKPLS", Response::Move(HashMap::new()));
+    assert_eq!(game.outcome(0, 2), Outcome::Win); // Paper covers rock
+    assert_eq!(game.outcome(2, 4), Outcome::Win); // Scissors cuts paper
+    assert_eq!(game.outcome(3, 0), Outcome::Win); // Rock crushes lizard
+    assert_eq!(game.outcome(1, 3), Outcome::Win); // Lizard poisons Spock
+    assert_eq!(game.outcome(4, 1), Outcome::Win); // Spock smashes scissors
+    assert_eq!(game.outcome(0, 4), Outcome::Lose); // Rock crushes scissors
+    assert_eq!(game.outcome(3, 3), Outcome::Draw);
+    for opponent in 0..5 {
+        for outcome in [Outcome::Lose, Outcome::Draw, Outcome::Win] {
+            assert_eq!(
+                game.outcome(opponent, game.move_for(opponent, outcome)),
+                outcome
+            );
+        }
+    }
+}
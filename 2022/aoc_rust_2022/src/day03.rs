@@ -0,0 +1,298 @@
+//! Solutions to 2022: Advent of Code day 3
+//! By Peter Fornwall
+
+use crate::sniff::{self, Confidence};
+use itertools::Itertools;
+use std::{collections::HashSet, fmt};
+
+pub type Input = Vec<Vec<i32>>;
+pub type Output = i32;
+
+/// Set of item priorities (1..=52) as a bitmask.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub const ALL: ItemSet = ItemSet(u64::MAX);
+
+    pub fn intersection(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 & other.0)
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn items(self) -> impl Iterator<Item = i32> {
+        (0..64).filter(move |i| self.0 & (1u64 << i) != 0)
+    }
+
+    /// Set of the priorities, or the first one that is not in `0..64`.
+    pub fn try_from_priorities(prios: impl IntoIterator<Item = i32>) -> Result<ItemSet, i32> {
+        prios.into_iter().try_fold(ItemSet(0), |acc, prio| {
+            let bit = u32::try_from(prio)
+                .ok()
+                .and_then(|prio| 1u64.checked_shl(prio))
+                .ok_or(prio)?;
+            Ok(ItemSet(acc.0 | bit))
+        })
+    }
+}
+
+/// Panics on priorities outside `0..64`, see [`ItemSet::try_from_priorities`].
+impl FromIterator<i32> for ItemSet {
+    fn from_iter<T: IntoIterator<Item = i32>>(iter: T) -> Self {
+        ItemSet::try_from_priorities(iter)
+            .unwrap_or_else(|prio| panic!("priority {prio} does not fit in an ItemSet"))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ItemError {
+    /// Rucksack can't be split into equally sized compartments
+    Uneven {
+        group: usize,
+    },
+    /// Last group has fewer rucksacks than the group size
+    Incomplete {
+        group: usize,
+    },
+    Empty {
+        group: usize,
+    },
+    Ambiguous {
+        group: usize,
+        items: Vec<i32>,
+    },
+    /// Priority that does not fit in an [`ItemSet`]
+    BadPriority {
+        group: usize,
+        priority: i32,
+    },
+    /// Byte on a line of the input that is not a letter
+    BadItem {
+        line: usize,
+        item: u8,
+    },
+    /// Groups or compartments of size 0
+    ZeroSize,
+}
+
+impl fmt::Display for ItemError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ItemError::Uneven { group } => write!(f, "group {group}: uneven compartments"),
+            ItemError::Incomplete { group } => write!(f, "group {group}: too few rucksacks"),
+            ItemError::Empty { group } => write!(f, "group {group}: no common item"),
+            ItemError::Ambiguous { group, items } => {
+                write!(f, "group {group}: several common items {items:?}")
+            }
+            ItemError::BadPriority { group, priority } => {
+                write!(f, "group {group}: priority {priority} out of range")
+            }
+            ItemError::BadItem { line, item } => {
+                write!(f, "line {line}: bad item {:?}", *item as char)
+            }
+            ItemError::ZeroSize => write!(f, "size must be at least 1"),
+        }
+    }
+}
+
+impl std::error::Error for ItemError {}
+
+fn item_set(group: usize, prios: &[i32]) -> Result<ItemSet, ItemError> {
+    ItemSet::try_from_priorities(prios.iter().copied())
+        .map_err(|priority| ItemError::BadPriority { group, priority })
+}
+
+/// The single item common to all sets of a group.
+pub fn common_item(
+    group: usize,
+    sets: impl IntoIterator<Item = ItemSet>,
+) -> Result<i32, ItemError> {
+    let common = sets.into_iter().fold(ItemSet::ALL, ItemSet::intersection);
+    match common.len() {
+        0 => Err(ItemError::Empty { group }),
+        1 => Ok(common.items().next().unwrap()),
+        _ => Err(ItemError::Ambiguous {
+            group,
+            items: common.items().collect(),
+        }),
+    }
+}
+
+/// Sum of the items common to all compartments of each rucksack.
+pub fn compartment_priorities(
+    input: &[Vec<i32>],
+    nr_compartments: usize,
+) -> Result<Output, ItemError> {
+    if nr_compartments == 0 {
+        return Err(ItemError::ZeroSize);
+    }
+    let mut sum = 0;
+    for (group, prios) in input.iter().enumerate() {
+        if prios.is_empty() || prios.len() % nr_compartments != 0 {
+            return Err(ItemError::Uneven { group });
+        }
+        let compartments = prios.chunks(prios.len() / nr_compartments);
+        let sets = compartments
+            .map(|c| item_set(group, c))
+            .collect::<Result<Vec<_>, _>>()?;
+        sum += common_item(group, sets)?;
+    }
+    Ok(sum)
+}
+
+/// Sum of the items common to all rucksacks in each group of `group_size` elves.
+pub fn group_priorities(input: &[Vec<i32>], group_size: usize) -> Result<Output, ItemError> {
+    if group_size == 0 {
+        return Err(ItemError::ZeroSize);
+    }
+    let mut sum = 0;
+    for (group, chunk) in input.chunks(group_size).enumerate() {
+        if chunk.len() != group_size {
+            return Err(ItemError::Incomplete { group });
+        }
+        let sets = chunk
+            .iter()
+            .map(|x| item_set(group, x))
+            .collect::<Result<Vec<_>, _>>()?;
+        sum += common_item(group, sets)?;
+    }
+    Ok(sum)
+}
+
+pub fn looks_like(input: &str) -> Confidence {
+    if !sniff::all_lines(input, |l| {
+        !l.is_empty() && l.len() % 2 == 0 && l.bytes().all(|c| c.is_ascii_alphabetic())
+    }) {
+        Confidence::No
+    } else if sniff::same_width(input) {
+        // A single line or a grid of letters is more likely another day
+        Confidence::Low
+    } else {
+        Confidence::High
+    }
+}
+
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn parse(content: &str) -> Result<Input, ItemError> {
+    content
+        .lines()
+        .enumerate()
+        .map(|(line_nr, line)| {
+            let mut v: Vec<i32> = Vec::new();
+            for &c in line.as_bytes() {
+                if c.is_ascii_lowercase() {
+                    v.push((c - b'a') as i32 + 1);
+                } else if c.is_ascii_uppercase() {
+                    v.push((c - b'A') as i32 + 27);
+                } else {
+                    return Err(ItemError::BadItem {
+                        line: line_nr + 1,
+                        item: c,
+                    });
+                }
+            }
+            Ok(v)
+        })
+        .collect()
+}
+
+pub fn part1(input: &Input) -> Output {
+    compartment_priorities(input, 2).unwrap()
+}
+
+pub fn part2(input: &Input) -> Output {
+    group_priorities(input, 3).unwrap()
+}
+
+/// Reference for `part1` with hash sets, kept to compare against.
+pub fn part1_hashset(input: &Input) -> Output {
+    input
+        .iter()
+        .map(|prios| {
+            let (s1, s2) = prios
+                .chunks(prios.len() / 2)
+                .map(|p| p.iter().collect::<HashSet<_>>())
+                .next_tuple()
+                .unwrap();
+            *s1.intersection(&s2).next().unwrap()
+        })
+        .sum()
+}
+
+/// Reference for `part2` with hash sets, kept to compare against.
+pub fn part2_hashset(input: &Input) -> Output {
+    input
+        .chunks(3)
+        .map(|chunk| {
+            let (s1, s2, s3) = chunk
+                .iter()
+                .map(|x| x.iter().copied().collect::<HashSet<_>>())
+                .next_tuple()
+                .unwrap();
+            *(&(&s1 & &s2) & &s3).iter().next().unwrap()
+        })
+        .sum()
+}
+
+#[test]
+fn test1() {
+    let input = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw";
+    let v = parse(input).unwrap();
+
+    let result_p1 = part1(&v);
+    println!("Part1: {}", result_p1);
+    assert_eq!(result_p1, 157);
+    let result_p2 = part2(&v);
+    println!("Part2: {}", result_p2);
+    assert_eq!(result_p2, 70);
+
+    assert_eq!(group_priorities(&v, 6), Err(ItemError::Empty { group: 0 }));
+    assert_eq!(
+        group_priorities(&v[..4], 3),
+        Err(ItemError::Incomplete { group: 1 })
+    );
+    let ambiguous = parse("abab").unwrap();
+    assert_eq!(
+        compartment_priorities(&ambiguous, 2),
+        Err(ItemError::Ambiguous {
+            group: 0,
+            items: vec![1, 2]
+        })
+    );
+
+    assert_eq!(compartment_priorities(&v, 0), Err(ItemError::ZeroSize));
+    assert_eq!(group_priorities(&v, 0), Err(ItemError::ZeroSize));
+    assert_eq!(
+        parse("abAB\nab1b\r\n"),
+        Err(ItemError::BadItem {
+            line: 2,
+            item: b'1'
+        })
+    );
+    assert_eq!(
+        compartment_priorities(&[vec![1, 64]], 2),
+        Err(ItemError::BadPriority {
+            group: 0,
+            priority: 64
+        })
+    );
+    assert_eq!(
+        group_priorities(&[vec![-1]], 1),
+        Err(ItemError::BadPriority {
+            group: 0,
+            priority: -1
+        })
+    );
+}
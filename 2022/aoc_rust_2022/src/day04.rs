@@ -0,0 +1,167 @@
+//! Solutions to 2022: Advent of Code day 4
+//! By Peter Fornwall
+
+use crate::sniff::{self, Confidence};
+use itertools::Itertools;
+
+pub type Input = Vec<(i32, i32, i32, i32)>;
+pub type Output = usize;
+
+pub fn looks_like(input: &str) -> Confidence {
+    sniff::all_lines(input, |l| {
+        let parts: Vec<_> = l.split(['-', ',']).collect();
+        parts.len() == 4 && parts.iter().all(|p| sniff::is_digits(p))
+    })
+    .into()
+}
+
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn parse(input: &str) -> Input {
+    input
+        .trim()
+        .lines()
+        .map(|line| {
+            line.split(['-', ','])
+                .map(|x| x.parse::<i32>().unwrap())
+                .collect_tuple()
+                .unwrap()
+        })
+        .collect()
+}
+
+pub fn part1(input: &Input) -> Output {
+    input
+        .iter()
+        // Check if one range fully contains the other
+        .filter(|x| (x.2 >= x.0 && x.3 <= x.1) || (x.0 >= x.2 && x.1 <= x.3))
+        .count()
+}
+
+pub fn part2(input: &Input) -> Output {
+    // Check how many overlap
+    input.iter().filter(|x| !(x.2 > x.1 || x.3 < x.0)).count()
+}
+
+/// Section range with the lower section first, as a reversed range like
+/// `5-3` covers the same sections as `3-5`.
+fn range(start: i32, end: i32) -> (i32, i32) {
+    (start.min(end), start.max(end))
+}
+
+/// Section ranges of all elves, two per pair, in input order.
+pub fn elves(input: &[(i32, i32, i32, i32)]) -> Vec<(i32, i32)> {
+    input
+        .iter()
+        .flat_map(|x| [range(x.0, x.1), range(x.2, x.3)])
+        .collect()
+}
+
+/// Number of sections assigned to both elves of a pair.
+pub fn overlap_len(pair: &(i32, i32, i32, i32)) -> i64 {
+    let (a, b) = (range(pair.0, pair.1), range(pair.2, pair.3));
+    (a.1.min(b.1) as i64 - a.0.max(b.0) as i64 + 1).max(0)
+}
+
+pub fn overlap_report(input: &[(i32, i32, i32, i32)]) -> Vec<i64> {
+    input.iter().map(overlap_len).collect()
+}
+
+/// Number of different sections assigned to any elf.
+pub fn sections_covered(input: &[(i32, i32, i32, i32)]) -> i64 {
+    let mut covered = 0;
+    let mut covered_to: Option<i64> = None;
+    for (start, end) in elves(input).into_iter().sorted() {
+        let (start, end) = (start as i64, end as i64);
+        let start = covered_to.map_or(start, |to| start.max(to + 1));
+        if end >= start {
+            covered += end - start + 1;
+            covered_to = Some(end);
+        }
+    }
+    covered
+}
+
+/// Section assigned to the most elves, and the number of elves (lowest section on ties).
+pub fn most_assigned(input: &[(i32, i32, i32, i32)]) -> Option<(i32, usize)> {
+    let mut events: Vec<(i64, i64)> = Vec::new();
+    for (start, end) in elves(input) {
+        events.push((start as i64, 1));
+        events.push((end as i64 + 1, -1));
+    }
+    events.sort_unstable();
+    let mut assigned = 0;
+    let mut best: Option<(i32, usize)> = None;
+    for (section, changes) in &events.into_iter().group_by(|e| e.0) {
+        assigned += changes.map(|e| e.1).sum::<i64>();
+        let count = usize::try_from(assigned).expect("every range ends after it starts");
+        if best.is_none_or(|(_, most)| count > most) {
+            // Only a start raises the count, and starts are sections
+            let section = i32::try_from(section).expect("the count rose at a start");
+            best = Some((section, count));
+        }
+    }
+    best
+}
+
+/// Fewest elves to drop so that no two remaining elves share a section,
+/// by keeping the elves that end first (interval scheduling).
+pub fn elves_to_drop(input: &[(i32, i32, i32, i32)]) -> Vec<usize> {
+    let elves = elves(input);
+    let mut order: Vec<usize> = (0..elves.len()).collect();
+    order.sort_by_key(|ix| (elves[*ix].1, elves[*ix].0));
+    let mut dropped = Vec::new();
+    let mut last_end: Option<i32> = None;
+    for ix in order {
+        if last_end.is_none_or(|end| elves[ix].0 > end) {
+            last_end = Some(elves[ix].1);
+        } else {
+            dropped.push(ix);
+        }
+    }
+    dropped.sort_unstable();
+    dropped
+}
+
+#[test]
+fn test1() {
+    let input = "2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8";
+    let v = parse(input);
+
+    let result_p1 = part1(&v);
+    println!("Part1: {}", result_p1);
+    assert_eq!(result_p1, 2);
+    let result_p2 = part2(&v);
+    println!("Part2: {}", result_p2);
+    assert_eq!(result_p2, 4);
+
+    assert_eq!(overlap_report(&v), [0, 0, 1, 5, 1, 3]);
+    assert_eq!(sections_covered(&v), 8);
+    assert_eq!(most_assigned(&v), Some((6, 8)));
+    assert_eq!(elves_to_drop(&v), [0, 1, 4, 6, 7, 9, 10, 11]);
+}
+
+#[test]
+fn test_extreme_ranges() {
+    // Reversed ranges cover the same sections as the ordered ones
+    let v = parse("5-3,4-4\n9-7,8-9");
+    assert_eq!(overlap_report(&v), [1, 2]);
+    assert_eq!(sections_covered(&v), 6);
+    assert_eq!(most_assigned(&v), Some((4, 2)));
+    assert_eq!(elves_to_drop(&v), [0, 3]);
+
+    // Ranges at the ends of i32 neither overflow nor lose sections
+    let full = (i32::MIN, i32::MAX, i32::MIN, i32::MAX);
+    assert_eq!(overlap_len(&full), 1 << 32);
+    assert_eq!(sections_covered(&[full]), 1 << 32);
+    assert_eq!(most_assigned(&[full]), Some((i32::MIN, 2)));
+    assert_eq!(elves_to_drop(&[full]), [1]);
+    let ends = (i32::MIN, i32::MIN, i32::MAX, i32::MAX);
+    assert_eq!(sections_covered(&[ends]), 2);
+    assert_eq!(most_assigned(&[ends]), Some((i32::MIN, 1)));
+    assert_eq!(elves_to_drop(&[ends]), []);
+}
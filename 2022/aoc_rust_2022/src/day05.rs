@@ -0,0 +1,529 @@
+//! Solutions to 2022: Advent of Code day 5
+//! By Peter Fornwall
+
+use crate::sniff::{self, Confidence};
+use std::fmt;
+
+/// Stacks of crates, bottom crate first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Crates {
+    pub stacks: Vec<Vec<u8>>,
+    /// Label of each stack, as written below the diagram
+    pub labels: Vec<usize>,
+}
+
+/// Move `count` crates from one stack index to another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instruction {
+    pub count: usize,
+    pub from: usize,
+    pub to: usize,
+}
+
+/// Errors from parsing the input, with 1-based line and column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    MissingDiagram,
+    MissingInstructions,
+    BadLabel {
+        line: usize,
+        column: usize,
+    },
+    BadCrate {
+        line: usize,
+        column: usize,
+    },
+    /// Crate with an empty place below it
+    FloatingCrate {
+        line: usize,
+        column: usize,
+    },
+    BadInstruction {
+        line: usize,
+    },
+    UnknownStack {
+        line: usize,
+        label: usize,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::MissingDiagram => write!(f, "no stack diagram"),
+            ParseError::MissingInstructions => write!(f, "no empty line before the instructions"),
+            ParseError::BadLabel { line, column } => {
+                write!(f, "line {line}, column {column}: bad stack label")
+            }
+            ParseError::BadCrate { line, column } => {
+                write!(f, "line {line}, column {column}: bad crate")
+            }
+            ParseError::FloatingCrate { line, column } => {
+                write!(f, "line {line}, column {column}: crate without support")
+            }
+            ParseError::BadInstruction { line } => write!(f, "line {line}: bad instruction"),
+            ParseError::UnknownStack { line, label } => {
+                write!(f, "line {line}: no stack labeled {label}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Errors from moving crates, with stacks given by their labels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoveError {
+    /// The 1-based `instruction` moves more crates than its source stack holds
+    NotEnoughCrates {
+        instruction: usize,
+        label: usize,
+        count: usize,
+        available: usize,
+    },
+    EmptyStack {
+        label: usize,
+    },
+    /// The 1-based `instruction` refers to a stack `index` that doesn't exist
+    UnknownStack {
+        instruction: usize,
+        index: usize,
+    },
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MoveError::NotEnoughCrates {
+                instruction,
+                label,
+                count,
+                available,
+            } => write!(
+                f,
+                "instruction {instruction}: can't move {count} crates from stack {label}, it has {available}"
+            ),
+            MoveError::EmptyStack { label } => write!(f, "stack {label} is empty"),
+            MoveError::UnknownStack { instruction, index } => {
+                write!(f, "instruction {instruction}: no stack at index {index}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for MoveError {}
+
+/// A crane model, deciding how lifted crates are put down.
+pub trait Crane {
+    /// Order of the `lifted` crates (bottom first, as they were on the source stack)
+    /// when put on the destination stack, bottom first.
+    fn arrange(&self, lifted: Vec<u8>) -> Vec<u8>;
+}
+
+/// Moves one crate at a time, reversing the order of the moved crates.
+pub struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn arrange(&self, mut lifted: Vec<u8>) -> Vec<u8> {
+        lifted.reverse();
+        lifted
+    }
+}
+
+/// Moves all crates at once, keeping their order.
+pub struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn arrange(&self, lifted: Vec<u8>) -> Vec<u8> {
+        lifted
+    }
+}
+
+/// Moves at most `capacity` crates at a time, keeping the order within each lift.
+/// Capacity 1 works like the CrateMover 9000.
+pub struct LimitedCrane {
+    pub capacity: usize,
+}
+
+impl Crane for LimitedCrane {
+    fn arrange(&self, lifted: Vec<u8>) -> Vec<u8> {
+        lifted
+            .rchunks(self.capacity.max(1))
+            .flatten()
+            .copied()
+            .collect()
+    }
+}
+
+impl Crates {
+    /// Runs one instruction, `nr` is its 1-based number used in errors.
+    pub fn apply(
+        &mut self,
+        crane: &impl Crane,
+        instruction: Instruction,
+        nr: usize,
+    ) -> Result<(), MoveError> {
+        let Instruction { count, from, to } = instruction;
+        if let Some(&index) = [from, to].iter().find(|&&ix| ix >= self.stacks.len()) {
+            return Err(MoveError::UnknownStack {
+                instruction: nr,
+                index,
+            });
+        }
+        let stack = &mut self.stacks[from];
+        if count > stack.len() {
+            return Err(MoveError::NotEnoughCrates {
+                instruction: nr,
+                label: self.labels[from],
+                count,
+                available: stack.len(),
+            });
+        }
+        let lifted = stack.split_off(stack.len() - count);
+        self.stacks[to].extend(crane.arrange(lifted));
+        Ok(())
+    }
+
+    /// States after each instruction. Stops after the first failing instruction.
+    pub fn replay<'a, C: Crane>(
+        &self,
+        crane: &'a C,
+        instructions: &'a [Instruction],
+    ) -> Replay<'a, C> {
+        Replay {
+            crates: self.clone(),
+            crane,
+            instructions,
+            next: 0,
+        }
+    }
+
+    /// Top crate of every stack.
+    pub fn tops(&self) -> Result<String, MoveError> {
+        self.stacks
+            .iter()
+            .zip(&self.labels)
+            .map(|(stack, label)| {
+                let c = stack
+                    .last()
+                    .ok_or(MoveError::EmptyStack { label: *label })?;
+                Ok(*c as char)
+            })
+            .collect()
+    }
+
+    /// The stacks drawn like the puzzle input, with labels below.
+    pub fn render(&self) -> String {
+        let height = self.stacks.iter().map(Vec::len).max().unwrap_or(0);
+        let mut s = String::new();
+        for h in (0..height).rev() {
+            let row: Vec<String> = self
+                .stacks
+                .iter()
+                .map(|stack| match stack.get(h) {
+                    Some(c) => format!("[{}]", *c as char),
+                    None => "   ".to_string(),
+                })
+                .collect();
+            s += &row.join(" ");
+            s.push('\n');
+        }
+        let labels: Vec<String> = self.labels.iter().map(|l| format!("{l:^3}")).collect();
+        s += &labels.join(" ");
+        s.push('\n');
+        s
+    }
+}
+
+pub struct Replay<'a, C> {
+    crates: Crates,
+    crane: &'a C,
+    instructions: &'a [Instruction],
+    next: usize,
+}
+
+impl<C: Crane> Iterator for Replay<'_, C> {
+    type Item = Result<Crates, MoveError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let instruction = *self.instructions.get(self.next)?;
+        self.next += 1;
+        match self.crates.apply(self.crane, instruction, self.next) {
+            Ok(()) => Some(Ok(self.crates.clone())),
+            Err(e) => {
+                self.next = self.instructions.len();
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+pub fn looks_like(input: &str) -> Confidence {
+    let mut lines = sniff::sample(input);
+    let has_crates = lines.by_ref().any(|l| l.trim_start().starts_with('['));
+    (has_crates && lines.any(|l| l.starts_with("move "))).into()
+}
+
+/// Space separated words of a line, with their byte offsets.
+fn words(line: &str) -> impl Iterator<Item = (usize, &str)> {
+    line.split(' ')
+        .scan(0, |pos, word| {
+            let start = *pos;
+            *pos += word.len() + 1;
+            Some((start, word))
+        })
+        .filter(|(_, word)| !word.is_empty())
+}
+
+/// Parses the stack diagram and the instructions. Stacks are found from the
+/// positions of the labels, so labels can have several digits and diagram
+/// lines don't need trailing spaces.
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn parse(input: &str) -> Result<(Crates, Vec<Instruction>), ParseError> {
+    let lines: Vec<&str> = input
+        .trim_start_matches(['\n', '\r'])
+        .lines()
+        .map(|line| line.trim_end_matches('\r'))
+        .collect();
+    let blank = lines
+        .iter()
+        .position(|line| line.trim().is_empty())
+        .ok_or(ParseError::MissingInstructions)?;
+    if blank == 0 {
+        return Err(ParseError::MissingDiagram);
+    }
+
+    let label_line = blank - 1;
+    let mut labels = Vec::new();
+    let mut spans = Vec::new();
+    for (start, word) in words(lines[label_line]) {
+        let label = word.parse::<usize>().ok().filter(|l| !labels.contains(l));
+        let label = label.ok_or(ParseError::BadLabel {
+            line: label_line + 1,
+            column: start + 1,
+        })?;
+        labels.push(label);
+        spans.push(start..start + word.len());
+    }
+
+    let mut stacks: Vec<Vec<u8>> = vec![Vec::new(); labels.len()];
+    for (line_nr, line) in lines.iter().enumerate().take(label_line).rev() {
+        let height = label_line - 1 - line_nr;
+        for (start, word) in words(line) {
+            let bad_crate = ParseError::BadCrate {
+                line: line_nr + 1,
+                column: start + 1,
+            };
+            let &[b'[', c, b']'] = word.as_bytes() else {
+                return Err(bad_crate);
+            };
+            let stack = spans
+                .iter()
+                .position(|span| span.start < start + 3 && start < span.end)
+                .ok_or(bad_crate)?;
+            if stacks[stack].len() != height {
+                return Err(ParseError::FloatingCrate {
+                    line: line_nr + 1,
+                    column: start + 1,
+                });
+            }
+            stacks[stack].push(c);
+        }
+    }
+
+    let mut instructions = Vec::new();
+    for (line_nr, line) in lines.iter().enumerate().skip(blank + 1) {
+        let line_nr = line_nr + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let bad_instruction = ParseError::BadInstruction { line: line_nr };
+        let words: Vec<_> = line.split_whitespace().collect();
+        let ["move", count, "from", from, "to", to] = words[..] else {
+            return Err(bad_instruction);
+        };
+        let count = count
+            .parse::<usize>()
+            .map_err(|_| bad_instruction.clone())?;
+        let stack_index = |label: &str| {
+            let label = label
+                .parse::<usize>()
+                .map_err(|_| bad_instruction.clone())?;
+            labels
+                .iter()
+                .position(|l| *l == label)
+                .ok_or(ParseError::UnknownStack {
+                    line: line_nr,
+                    label,
+                })
+        };
+        instructions.push(Instruction {
+            count,
+            from: stack_index(from)?,
+            to: stack_index(to)?,
+        });
+    }
+    Ok((Crates { stacks, labels }, instructions))
+}
+
+/// Top crates after running all instructions with the given crane.
+pub fn solve(input: &str, crane: &impl Crane) -> Result<String, Box<dyn std::error::Error>> {
+    let (mut crates, instructions) = parse(input)?;
+    for (nr, instruction) in (1..).zip(instructions) {
+        crates.apply(crane, instruction, nr)?;
+    }
+    Ok(crates.tops()?)
+}
+
+pub fn part1(input: &str) -> String {
+    solve(input, &CrateMover9000).unwrap()
+}
+
+pub fn part2(input: &str) -> String {
+    solve(input, &CrateMover9001).unwrap()
+}
+
+#[test]
+fn test1() {
+    let input = concat!(
+        "    [D]    \n",
+        "[N] [C]    \n",
+        "[Z] [M] [P]\n",
+        " 1   2   3 \n",
+        "\n",
+        "move 1 from 2 to 1\n",
+        "move 3 from 1 to 3\n",
+        "move 2 from 2 to 1\n",
+        "move 1 from 1 to 2\n",
+    );
+
+    let result_p1 = part1(input);
+    println!("Part1: {}", result_p1);
+    assert_eq!(result_p1, "CMZ");
+    let result_p2 = part2(input);
+    println!("Part2: {}", result_p2);
+    assert_eq!(result_p2, "MCD");
+}
+
+#[test]
+fn test_parse_wide_and_ragged() {
+    let input = concat!(
+        "                                    [K]\n",
+        "[A] [B] [C] [D] [E] [F] [G] [H] [I] [J]  [L]\n",
+        " 1   2   3   4   5   6   7   8   9  10   11\n",
+        "\n",
+        "move 1 from 10 to 11\n",
+    );
+    let (crates, instructions) = parse(input).unwrap();
+    assert_eq!(crates.labels, (1..=11).collect::<Vec<_>>());
+    assert_eq!(crates.stacks[9], b"JK");
+    assert_eq!(crates.stacks[10], b"L");
+    assert_eq!(
+        instructions,
+        [Instruction {
+            count: 1,
+            from: 9,
+            to: 10
+        }]
+    );
+    assert_eq!(part1(input), "ABCDEFGHIJK");
+
+    let bad = |input: &str| parse(input).err().unwrap();
+    assert_eq!(
+        bad("[A]\n 1\nmove 1 from 1 to 1"),
+        ParseError::MissingInstructions
+    );
+    assert_eq!(
+        bad("[A]\n x\n\n"),
+        ParseError::BadLabel { line: 2, column: 2 }
+    );
+    assert_eq!(
+        bad("[A] B\n 1  2\n\n"),
+        ParseError::BadCrate { line: 1, column: 5 }
+    );
+    assert_eq!(
+        bad("    [B]\n[A]\n 1   2\n\n"),
+        ParseError::FloatingCrate { line: 1, column: 5 }
+    );
+    assert_eq!(
+        bad("[A]\n 1\n\nmove 1 to 1"),
+        ParseError::BadInstruction { line: 4 }
+    );
+    assert_eq!(
+        bad("[A]\n 1\n\nmove 1 from 1 to 2"),
+        ParseError::UnknownStack { line: 4, label: 2 }
+    );
+}
+
+#[test]
+fn test_cranes_and_replay() {
+    let input = concat!(
+        "    [D]    \n",
+        "[N] [C]    \n",
+        "[Z] [M] [P]\n",
+        " 1   2   3 \n",
+        "\n",
+        "move 1 from 2 to 1\n",
+        "move 3 from 1 to 3\n",
+        "move 2 from 2 to 1\n",
+        "move 1 from 1 to 2\n",
+    );
+    let (crates, instructions) = parse(input).unwrap();
+    assert_eq!(
+        crates.render(),
+        input.split("\n\n").next().unwrap().to_string() + "\n"
+    );
+    assert_eq!(solve(input, &LimitedCrane { capacity: 1 }).unwrap(), "CMZ");
+    assert_eq!(solve(input, &LimitedCrane { capacity: 3 }).unwrap(), "MCD");
+    assert_eq!(solve(input, &LimitedCrane { capacity: 2 }).unwrap(), "MCZ");
+
+    let states: Vec<_> = crates
+        .replay(&CrateMover9000, &instructions)
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(states.len(), 4);
+    assert_eq!(
+        states[1].render(),
+        concat!(
+            "        [Z]\n",
+            "        [N]\n",
+            "    [C] [D]\n",
+            "    [M] [P]\n",
+            " 1   2   3 \n",
+        )
+    );
+    assert_eq!(parse(&(states[1].render() + "\n")).unwrap().0, states[1]);
+
+    let too_many = [Instruction {
+        count: 4,
+        from: 0,
+        to: 1,
+    }];
+    let mut replay = crates.replay(&CrateMover9001, &too_many);
+    assert_eq!(
+        replay.next(),
+        Some(Err(MoveError::NotEnoughCrates {
+            instruction: 1,
+            label: 1,
+            count: 4,
+            available: 2
+        }))
+    );
+    assert_eq!(replay.next(), None);
+
+    for (from, to) in [(3, 0), (0, 3)] {
+        let unknown = [Instruction { count: 0, from, to }];
+        assert_eq!(
+            crates.replay(&CrateMover9000, &unknown).next(),
+            Some(Err(MoveError::UnknownStack {
+                instruction: 1,
+                index: 3
+            }))
+        );
+    }
+    assert!(solve(
+        &input.replace("move 1 from 1 to 2", "move 3 from 1 to 2"),
+        &CrateMover9000
+    )
+    .is_err());
+}
@@ -0,0 +1,151 @@
+//! Solutions to 2022: Advent of Code day 6
+//! By Peter Fornwall
+
+use crate::sniff::Confidence;
+use rand::{rngs::StdRng, Rng};
+use std::{
+    collections::HashSet,
+    io::{self, BufReader, Read},
+};
+
+pub fn looks_like(input: &str) -> Confidence {
+    let s = input.trim();
+    (!s.is_empty() && s.bytes().all(|c| c.is_ascii_lowercase())).into()
+}
+
+/// Sliding window over a datastream, fed one character at a time. Keeps a
+/// count of every character in the current window, so each step is O(1).
+pub struct MarkerDetector {
+    nr: usize,
+    window: Vec<u8>,
+    counts: [usize; 256],
+    distinct: usize,
+    position: usize,
+}
+
+impl MarkerDetector {
+    /// Detector for markers of `nr` distinct characters. There are no markers
+    /// of 0 characters, so with `nr` 0 nothing is ever detected.
+    pub fn new(nr: usize) -> Self {
+        MarkerDetector {
+            nr,
+            window: vec![0; nr],
+            counts: [0; 256],
+            distinct: 0,
+            position: 0,
+        }
+    }
+
+    /// Adds the next character. Returns the position after it if the last
+    /// `nr` characters are all different.
+    pub fn push(&mut self, c: u8) -> Option<usize> {
+        if self.nr == 0 {
+            return None;
+        }
+        let slot = self.position % self.nr;
+        if self.position >= self.nr {
+            let old = self.window[slot] as usize;
+            self.counts[old] -= 1;
+            if self.counts[old] == 0 {
+                self.distinct -= 1;
+            }
+        }
+        self.window[slot] = c;
+        self.counts[c as usize] += 1;
+        if self.counts[c as usize] == 1 {
+            self.distinct += 1;
+        }
+        self.position += 1;
+        (self.distinct == self.nr).then_some(self.position)
+    }
+}
+
+/// Positions after every marker of `nr` distinct characters in the stream.
+/// Whitespace at the end of the stream is ignored, elsewhere it is a character
+/// like any other.
+pub fn markers<R: Read>(reader: R, nr: usize) -> impl Iterator<Item = io::Result<usize>> {
+    let mut detector = MarkerDetector::new(nr);
+    // Whitespace is held back until it turns out not to be at the end
+    let mut pending = Vec::new();
+    BufReader::new(reader).bytes().flat_map(move |c| {
+        let mut found = Vec::new();
+        match c {
+            Ok(c) if c.is_ascii_whitespace() => pending.push(c),
+            Ok(c) => {
+                for c in pending.drain(..).chain([c]) {
+                    found.extend(detector.push(c).map(Ok));
+                }
+            }
+            Err(e) => found.push(Err(e)),
+        }
+        found
+    })
+}
+
+/// Position after the first `nr` distinct characters.
+pub fn find_unique(content: &str, nr: usize) -> Option<usize> {
+    markers(content.as_bytes(), nr)
+        .next()
+        .map(|position| position.unwrap())
+}
+
+/// Reference for `find_unique`, checking every window with a new set.
+pub fn find_unique_reference(content: &str, nr: usize) -> Option<usize> {
+    if nr == 0 {
+        return None;
+    }
+    let s = content.trim_end().as_bytes();
+    for i in 0..(s.len() + 1).saturating_sub(nr) {
+        let mut h: HashSet<u8> = HashSet::new();
+        if (0..nr).all(|x| h.insert(s[i + x])) {
+            return Some(i + nr);
+        }
+    }
+    None
+}
+
+/// Random datastream over a small alphabet, so that not all have markers.
+pub fn generate(rng: &mut StdRng) -> String {
+    let len = rng.gen_range(1..200);
+    let letters = rng.gen_range(4..=16);
+    (0..len)
+        .map(|_| (b'a' + rng.gen_range(0..letters)) as char)
+        .collect()
+}
+
+pub fn part1(input: &str) -> Option<usize> {
+    find_unique(input, 4)
+}
+
+pub fn part2(input: &str) -> Option<usize> {
+    find_unique(input, 14)
+}
+
+#[test]
+fn test1() {
+    let examples = [
+        ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+        ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+        ("nppdvjthqldpwncqszvftbjlhr", 6, 23),
+        ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+        ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+    ];
+    for (input, p1, p2) in examples {
+        assert_eq!(part1(input), Some(p1));
+        assert_eq!(part2(input), Some(p2));
+        assert_eq!(find_unique_reference(input, 14), Some(p2));
+    }
+
+    assert_eq!(find_unique("aaaabbb", 2), Some(5));
+    assert_eq!(find_unique("abab", 3), None);
+    let all: Vec<usize> = markers("abcab\n".as_bytes(), 3)
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(all, [3, 4, 5]);
+    // Only whitespace at the end is dropped
+    assert_eq!(find_unique("aa b\n\n", 3), Some(4));
+    assert_eq!(find_unique("aab \n", 3), None);
+    assert_eq!(find_unique_reference("aa b\n\n", 3), Some(4));
+    assert_eq!(find_unique("abc", 0), None);
+    assert_eq!(find_unique_reference("abc", 0), None);
+}
@@ -0,0 +1,796 @@
+//! Solutions to 2022: Advent of Code day 7
+//! By Peter Fornwall
+
+use crate::sniff::Confidence;
+use std::{collections::BTreeMap, fmt, io, path::Path};
+
+/// Index of the root directory in `FileSystem::dirs`.
+pub const ROOT: usize = 0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Entry {
+    /// Index of the directory in `FileSystem::dirs`
+    Dir(usize),
+    /// Size of the file
+    File(u64),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dir {
+    pub name: String,
+    pub parent: Option<usize>,
+    /// Entries by name, so listing a directory again doesn't add anything twice
+    pub entries: BTreeMap<String, Entry>,
+}
+
+/// Directory tree seen in a terminal session. Directories are stored in the
+/// order they were found, so every directory comes after its parent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileSystem {
+    pub dirs: Vec<Dir>,
+}
+
+/// Errors from parsing the session, with 1-based line numbers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    UnknownCommand {
+        line: usize,
+    },
+    BadEntry {
+        line: usize,
+    },
+    /// Directory listing without a `$ ls` before it
+    EntryOutsideLs {
+        line: usize,
+    },
+    /// Name used both for a file and a directory
+    Conflict {
+        line: usize,
+        name: String,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::UnknownCommand { line } => write!(f, "line {line}: unknown command"),
+            ParseError::BadEntry { line } => write!(f, "line {line}: bad directory entry"),
+            ParseError::EntryOutsideLs { line } => {
+                write!(f, "line {line}: directory entry without ls")
+            }
+            ParseError::Conflict { line, name } => {
+                write!(f, "line {line}: {name} is both a file and a directory")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl Default for FileSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FileSystem {
+    /// File system with only the root directory.
+    pub fn new() -> Self {
+        FileSystem {
+            dirs: vec![Dir {
+                name: String::new(),
+                parent: None,
+                entries: BTreeMap::new(),
+            }],
+        }
+    }
+
+    /// Subdirectory `name` of `parent`, created if missing. None if it is a file
+    /// or not a plain name.
+    pub fn add_dir(&mut self, parent: usize, name: &str) -> Option<usize> {
+        if !is_plain_name(name) {
+            return None;
+        }
+        match self.dirs[parent].entries.get(name) {
+            Some(Entry::Dir(dir)) => Some(*dir),
+            Some(Entry::File(_)) => None,
+            None => {
+                let dir = self.dirs.len();
+                self.dirs.push(Dir {
+                    name: name.to_string(),
+                    parent: Some(parent),
+                    entries: BTreeMap::new(),
+                });
+                self.dirs[parent]
+                    .entries
+                    .insert(name.to_string(), Entry::Dir(dir));
+                Some(dir)
+            }
+        }
+    }
+
+    /// Adds or replaces a file. Returns false if `name` is a directory or not a
+    /// plain name.
+    pub fn add_file(&mut self, dir: usize, name: &str, size: u64) -> bool {
+        if !is_plain_name(name) {
+            return false;
+        }
+        let entries = &mut self.dirs[dir].entries;
+        if let Some(Entry::Dir(_)) = entries.get(name) {
+            return false;
+        }
+        entries.insert(name.to_string(), Entry::File(size));
+        true
+    }
+
+    /// Follows an absolute or relative `path` from `dir`, creating missing
+    /// directories. `..` at the root stays at the root, like in a shell.
+    /// None if a part of the path is a file.
+    pub fn cd(&mut self, dir: usize, path: &str) -> Option<usize> {
+        let mut dir = if path.starts_with('/') { ROOT } else { dir };
+        for name in path.split('/').filter(|name| !name.is_empty()) {
+            dir = match name {
+                "." => dir,
+                ".." => self.dirs[dir].parent.unwrap_or(ROOT),
+                name => self.add_dir(dir, name)?,
+            };
+        }
+        Some(dir)
+    }
+
+    /// Directory at an absolute or relative `path` from `dir`, if seen.
+    pub fn lookup(&self, dir: usize, path: &str) -> Option<usize> {
+        let mut dir = if path.starts_with('/') { ROOT } else { dir };
+        for name in path.split('/').filter(|name| !name.is_empty()) {
+            dir = match name {
+                "." => dir,
+                ".." => self.dirs[dir].parent.unwrap_or(ROOT),
+                name => match self.dirs[dir].entries.get(name)? {
+                    Entry::Dir(dir) => *dir,
+                    Entry::File(_) => return None,
+                },
+            };
+        }
+        Some(dir)
+    }
+
+    /// Absolute path of a directory.
+    pub fn path(&self, dir: usize) -> String {
+        let mut names = Vec::new();
+        let mut current = dir;
+        while let Some(parent) = self.dirs[current].parent {
+            names.push(self.dirs[current].name.as_str());
+            current = parent;
+        }
+        names.reverse();
+        format!("/{}", names.join("/"))
+    }
+
+    /// Total size of a directory, including all subdirectories.
+    pub fn du(&self, dir: usize) -> u64 {
+        self.dirs[dir]
+            .entries
+            .values()
+            .map(|entry| match entry {
+                Entry::Dir(dir) => self.du(*dir),
+                Entry::File(size) => *size,
+            })
+            .sum()
+    }
+
+    /// Total size of every directory, by index.
+    pub fn sizes(&self) -> Vec<u64> {
+        let mut sizes: Vec<u64> = self
+            .dirs
+            .iter()
+            .map(|dir| {
+                dir.entries
+                    .values()
+                    .map(|entry| match entry {
+                        Entry::File(size) => *size,
+                        Entry::Dir(_) => 0,
+                    })
+                    .sum()
+            })
+            .collect();
+        // Children come after their parents, so they are done first
+        for dir in (1..self.dirs.len()).rev() {
+            let parent = self.dirs[dir].parent.unwrap();
+            sizes[parent] += sizes[dir];
+        }
+        sizes
+    }
+
+    /// Directories with a total size of at most `max_size`, like `find -size`.
+    pub fn find(&self, max_size: u64) -> Vec<usize> {
+        let sizes = self.sizes();
+        (0..self.dirs.len())
+            .filter(|dir| sizes[*dir] <= max_size)
+            .collect()
+    }
+
+    /// The tree drawn like in the puzzle description.
+    pub fn tree(&self) -> String {
+        let mut s = String::from("- / (dir)\n");
+        self.tree_entries(ROOT, 1, &mut s);
+        s
+    }
+
+    fn tree_entries(&self, dir: usize, depth: usize, s: &mut String) {
+        for (name, entry) in &self.dirs[dir].entries {
+            let indent = "  ".repeat(depth);
+            match entry {
+                Entry::Dir(dir) => {
+                    *s += &format!("{indent}- {name} (dir)\n");
+                    self.tree_entries(*dir, depth + 1, s);
+                }
+                Entry::File(size) => *s += &format!("{indent}- {name} (file, size={size})\n"),
+            }
+        }
+    }
+
+    /// The tree as nested JSON objects, directories with their total size.
+    pub fn to_json(&self) -> String {
+        let sizes = self.sizes();
+        let mut s = String::new();
+        self.json_dir(ROOT, "/", &sizes, &mut s);
+        s
+    }
+
+    fn json_dir(&self, dir: usize, name: &str, sizes: &[u64], s: &mut String) {
+        *s += &format!(
+            "{{\"name\":{},\"type\":\"dir\",\"size\":{},\"entries\":[",
+            json_string(name),
+            sizes[dir]
+        );
+        for (i, (name, entry)) in self.dirs[dir].entries.iter().enumerate() {
+            if i > 0 {
+                s.push(',');
+            }
+            match entry {
+                Entry::Dir(dir) => self.json_dir(*dir, name, sizes, s),
+                Entry::File(size) => {
+                    *s += &format!(
+                        "{{\"name\":{},\"type\":\"file\",\"size\":{size}}}",
+                        json_string(name)
+                    )
+                }
+            }
+        }
+        *s += "]}";
+    }
+
+    /// Shell script recreating the tree with sparse files of the right sizes,
+    /// in the directory given as first argument or a new temporary directory.
+    /// Prints the directory it used. Entry names are plain names, so every path
+    /// stays inside that directory.
+    pub fn to_shell_script(&self) -> String {
+        let mut s = String::from("#!/bin/sh\nset -e\nroot=\"${1:-$(mktemp -d)}\"\n");
+        for dir in 1..self.dirs.len() {
+            s += &format!("mkdir -p \"$root\"{}\n", self.shell_path(dir, ""));
+        }
+        for (dir, d) in self.dirs.iter().enumerate() {
+            for (name, entry) in &d.entries {
+                if let Entry::File(size) = entry {
+                    s += &format!(
+                        "truncate -s {size} \"$root\"{}\n",
+                        self.shell_path(dir, name)
+                    );
+                }
+            }
+        }
+        s += "echo \"$root\"\n";
+        s
+    }
+
+    /// Quoted path of `name` in `dir`, relative to the root, starting with `/`.
+    fn shell_path(&self, dir: usize, name: &str) -> String {
+        let path = self.path(dir);
+        let names = path.split('/').skip(1).chain([name]);
+        names
+            .filter(|name| !name.is_empty())
+            .map(|name| format!("/'{}'", name.replace('\'', r"'\''")))
+            .collect()
+    }
+
+    /// Terminal session exploring the whole tree, which parses back into it.
+    pub fn transcript(&self) -> String {
+        let mut s = String::from("$ cd /\n");
+        self.transcript_dir(ROOT, &mut s);
+        s
+    }
+
+    fn transcript_dir(&self, dir: usize, s: &mut String) {
+        *s += "$ ls\n";
+        for (name, entry) in &self.dirs[dir].entries {
+            match entry {
+                Entry::Dir(_) => *s += &format!("dir {name}\n"),
+                Entry::File(size) => *s += &format!("{size} {name}\n"),
+            }
+        }
+        for (name, entry) in &self.dirs[dir].entries {
+            if let Entry::Dir(child) = entry {
+                *s += &format!("$ cd {name}\n");
+                self.transcript_dir(*child, s);
+                *s += "$ cd ..\n";
+            }
+        }
+    }
+
+    /// Reads a real directory from disk, with apparent file sizes. Unlike
+    /// `du -b`, directories themselves count as 0 bytes. Symbolic links are skipped.
+    pub fn from_dir(path: &Path) -> io::Result<Self> {
+        let mut fs = FileSystem::new();
+        fs.read_dir(ROOT, path)?;
+        Ok(fs)
+    }
+
+    fn read_dir(&mut self, dir: usize, path: &Path) -> io::Result<()> {
+        let mut subdirs = Vec::new();
+        for entry in std::fs::read_dir(path)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                subdirs.push((name, entry.path()));
+            } else if file_type.is_file() {
+                self.add_file(dir, &name, entry.metadata()?.len());
+            }
+        }
+        // Same order as parsing a transcript: list everything, then visit by name
+        subdirs.sort();
+        let subdirs: Vec<_> = subdirs
+            .into_iter()
+            .filter_map(|(name, path)| Some((self.add_dir(dir, &name)?, path)))
+            .collect();
+        for (child, path) in subdirs {
+            self.read_dir(child, &path)?;
+        }
+        Ok(())
+    }
+}
+
+/// Directories to delete, and the space that frees.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    pub paths: Vec<String>,
+    pub freed: u64,
+}
+
+/// The search for a set of directories to delete ran out of steps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchLimit {
+    /// Best plan found before giving up, not necessarily the best one
+    pub best: Plan,
+}
+
+impl fmt::Display for SearchLimit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "search gave up, best plan so far frees {}",
+            self.best.freed
+        )
+    }
+}
+
+impl std::error::Error for SearchLimit {}
+
+impl FileSystem {
+    /// Space to free so that `required` bytes are unused on a disk of `disk_size` bytes.
+    pub fn space_to_free(&self, disk_size: u64, required: u64) -> u64 {
+        let unused = disk_size.saturating_sub(self.du(ROOT));
+        required.saturating_sub(unused)
+    }
+
+    /// The smallest single directory freeing enough space, None if even the
+    /// root directory is not enough.
+    pub fn plan_cleanup(&self, disk_size: u64, required: u64) -> Option<Plan> {
+        let to_free = self.space_to_free(disk_size, required);
+        let sizes = self.sizes();
+        let dir = (0..self.dirs.len())
+            .filter(|dir| sizes[*dir] >= to_free)
+            .min_by_key(|dir| sizes[*dir])?;
+        Some(Plan {
+            paths: vec![self.path(dir)],
+            freed: sizes[dir],
+        })
+    }
+
+    /// The directories, none inside another, freeing the least space that is
+    /// still enough. A branch and bound search, so it does not depend on the
+    /// sizes in bytes, but picking a subset is hard in general: after
+    /// `max_steps` steps it gives up with the best plan found so far.
+    pub fn plan_cleanup_set(
+        &self,
+        disk_size: u64,
+        required: u64,
+        max_steps: usize,
+    ) -> Result<Option<Plan>, SearchLimit> {
+        let to_free = self.space_to_free(disk_size, required);
+        if to_free == 0 {
+            return Ok(Some(Plan {
+                paths: Vec::new(),
+                freed: 0,
+            }));
+        }
+        // The smallest single directory bounds the search from above
+        let Some(single) = self.plan_cleanup(disk_size, required) else {
+            return Ok(None);
+        };
+        let sizes = self.sizes();
+        // Directories in preorder, with the position after their subtree
+        let mut order = Vec::new();
+        self.preorder(ROOT, &mut order);
+        let n = order.len();
+        // Most that directories at positions i.. can free, by deleting the
+        // topmost ones
+        let mut most = vec![0u64; n + 1];
+        for i in (0..n).rev() {
+            let (dir, end) = order[i];
+            most[i] = sizes[dir] + most[end];
+        }
+
+        // Each frame tries the positions from `next` on, on top of the
+        // directories picked by the frames below it
+        struct Frame {
+            next: usize,
+            freed: u64,
+        }
+        let mut best = (single.freed, Vec::new());
+        let mut picked: Vec<usize> = Vec::new();
+        let mut stack = vec![Frame { next: 0, freed: 0 }];
+        let mut steps = 0;
+        while let Some(frame) = stack.last_mut() {
+            let (i, freed) = (frame.next, frame.freed);
+            if i >= n || freed + most[i] < to_free {
+                stack.pop();
+                picked.pop();
+                continue;
+            }
+            steps += 1;
+            if steps > max_steps {
+                let best = self.plan_from(&order, best.0, &best.1).unwrap_or(single);
+                return Err(SearchLimit { best });
+            }
+            // Skipping position i is tried when coming back to this frame
+            frame.next += 1;
+            let (dir, end) = order[i];
+            let with = freed + sizes[dir];
+            if with >= to_free {
+                if with < best.0 {
+                    best = (with, picked.iter().copied().chain([i]).collect());
+                    if with == to_free {
+                        break;
+                    }
+                }
+            } else if with < best.0 {
+                picked.push(i);
+                stack.push(Frame {
+                    next: end,
+                    freed: with,
+                });
+            }
+        }
+        Ok(Some(
+            self.plan_from(&order, best.0, &best.1).unwrap_or(single),
+        ))
+    }
+
+    /// Plan deleting the directories at `positions` in preorder, None if there
+    /// are none.
+    fn plan_from(&self, order: &[(usize, usize)], freed: u64, positions: &[usize]) -> Option<Plan> {
+        if positions.is_empty() {
+            return None;
+        }
+        Some(Plan {
+            paths: positions.iter().map(|&i| self.path(order[i].0)).collect(),
+            freed,
+        })
+    }
+
+    fn preorder(&self, dir: usize, order: &mut Vec<(usize, usize)>) {
+        let position = order.len();
+        order.push((dir, 0));
+        for entry in self.dirs[dir].entries.values() {
+            if let Entry::Dir(child) = entry {
+                self.preorder(*child, order);
+            }
+        }
+        order[position].1 = order.len();
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => quoted += "\\\"",
+            '\\' => quoted += "\\\\",
+            c if c.is_control() => quoted += &format!("\\u{:04x}", c as u32),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// A name that can only mean an entry of its directory: not empty, `.` or `..`,
+/// and without `/`.
+fn is_plain_name(name: &str) -> bool {
+    !matches!(name, "" | "." | "..") && !name.contains('/')
+}
+
+pub fn looks_like(input: &str) -> Confidence {
+    input.trim_start().starts_with("$ cd ").into()
+}
+
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn parse(input: &str) -> Result<FileSystem, ParseError> {
+    let mut fs = FileSystem::new();
+    let mut cwd = ROOT;
+    let mut in_ls = false;
+    for (line_nr, line) in input.lines().enumerate() {
+        let line_nr = line_nr + 1;
+        let line = line.trim_end_matches('\r');
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(command) = line.strip_prefix("$ ") {
+            in_ls = false;
+            match command.split_once(' ') {
+                Some(("cd", path)) => {
+                    cwd = fs.cd(cwd, path).ok_or(ParseError::Conflict {
+                        line: line_nr,
+                        name: path.to_string(),
+                    })?;
+                }
+                None if command == "ls" => in_ls = true,
+                _ => return Err(ParseError::UnknownCommand { line: line_nr }),
+            }
+            continue;
+        }
+        if !in_ls {
+            return Err(ParseError::EntryOutsideLs { line: line_nr });
+        }
+        // Names can contain spaces
+        let Some((kind, name)) = line.split_once(' ') else {
+            return Err(ParseError::BadEntry { line: line_nr });
+        };
+        if !is_plain_name(name) {
+            return Err(ParseError::BadEntry { line: line_nr });
+        }
+        let added = if kind == "dir" {
+            fs.add_dir(cwd, name).is_some()
+        } else {
+            let size = kind
+                .parse::<u64>()
+                .map_err(|_| ParseError::BadEntry { line: line_nr })?;
+            fs.add_file(cwd, name, size)
+        };
+        if !added {
+            return Err(ParseError::Conflict {
+                line: line_nr,
+                name: name.to_string(),
+            });
+        }
+    }
+    Ok(fs)
+}
+
+pub fn part1(input: &str) -> u64 {
+    let fs = parse(input).unwrap();
+    fs.sizes().iter().filter(|size| **size <= 100_000).sum()
+}
+
+pub fn part2(input: &str) -> u64 {
+    let fs = parse(input).unwrap();
+    fs.plan_cleanup(70_000_000, 30_000_000).unwrap().freed
+}
+
+#[test]
+fn test1() {
+    let input = include_str!("../../inputs/day07_example1.txt");
+
+    let result_p1 = part1(input);
+    println!("Part1: {}", result_p1);
+    assert_eq!(result_p1, 95437);
+    let result_p2 = part2(input);
+    println!("Part2: {}", result_p2);
+    assert_eq!(result_p2, 24933642);
+
+    let fs = parse(input).unwrap();
+    assert_eq!(
+        fs.tree(),
+        concat!(
+            "- / (dir)\n",
+            "  - a (dir)\n",
+            "    - e (dir)\n",
+            "      - i (file, size=584)\n",
+            "    - f (file, size=29116)\n",
+            "    - g (file, size=2557)\n",
+            "    - h.lst (file, size=62596)\n",
+            "  - b.txt (file, size=14848514)\n",
+            "  - c.dat (file, size=8504156)\n",
+            "  - d (dir)\n",
+            "    - d.ext (file, size=5626152)\n",
+            "    - d.log (file, size=8033020)\n",
+            "    - j (file, size=4060174)\n",
+            "    - k (file, size=7214296)\n",
+        )
+    );
+    let e = fs.lookup(ROOT, "/a/e").unwrap();
+    assert_eq!(fs.path(e), "/a/e");
+    assert_eq!(fs.du(e), 584);
+    assert_eq!(fs.lookup(e, "../../d"), fs.lookup(ROOT, "d"));
+    let small: Vec<_> = fs.find(100_000).iter().map(|dir| fs.path(*dir)).collect();
+    assert_eq!(small, ["/a", "/a/e"]);
+}
+
+#[test]
+fn test_session() {
+    // Absolute cd in the middle, relisting a directory, and sizes above i32
+    let input = "$ cd /\n$ cd x/y\n$ ls\n3000000000 big\n$ cd /\n$ ls\ndir x\n1 a\n$ cd /x/y\n$ ls\n3000000000 big\n$ cd ../../..\n$ ls\n1 a";
+    let fs = parse(input).unwrap();
+    assert_eq!(fs.dirs.len(), 3);
+    assert_eq!(fs.du(ROOT), 3_000_000_001);
+    assert_eq!(fs.sizes(), [3_000_000_001, 3_000_000_000, 3_000_000_000]);
+
+    assert_eq!(
+        parse("$ ls\ndir a\na b").unwrap_err(),
+        ParseError::BadEntry { line: 3 }
+    );
+    let spaces = parse("$ ls\ndir my dir\n$ cd my dir\n$ ls\n5 a file").unwrap();
+    assert_eq!(
+        spaces.path(spaces.lookup(ROOT, "my dir").unwrap()),
+        "/my dir"
+    );
+    assert_eq!(spaces.du(ROOT), 5);
+    for entry in [
+        "dir ..", "dir .", "dir a/b", "5 ..", "5 a/b", "5 /a", "dir ",
+    ] {
+        assert_eq!(
+            parse(&format!("$ ls\n{entry}")).unwrap_err(),
+            ParseError::BadEntry { line: 2 }
+        );
+    }
+    let mut fs = FileSystem::new();
+    assert_eq!(fs.add_dir(ROOT, ".."), None);
+    assert!(!fs.add_file(ROOT, "a/b", 1));
+    assert_eq!(fs, FileSystem::new());
+    // Larger than the disk, so everything has to go
+    assert_eq!(part2("$ ls\n80000000 a"), 80_000_000);
+    assert_eq!(
+        parse("$ cd /\n1 a").unwrap_err(),
+        ParseError::EntryOutsideLs { line: 2 }
+    );
+    assert_eq!(
+        parse("$ ls\n1 a\n$ cd a").unwrap_err(),
+        ParseError::Conflict {
+            line: 3,
+            name: "a".to_string()
+        }
+    );
+    assert_eq!(
+        parse("$ rm -rf /").unwrap_err(),
+        ParseError::UnknownCommand { line: 1 }
+    );
+}
+
+#[test]
+fn test_export() {
+    let fs = parse(include_str!("../../inputs/day07_example1.txt")).unwrap();
+    assert_eq!(parse(&fs.transcript()).unwrap(), fs);
+    let json = fs.to_json();
+    assert!(json.starts_with(r#"{"name":"/","type":"dir","size":48381165,"entries":[{"name":"a","#));
+    assert!(json.contains(r#"{"name":"i","type":"file","size":584}]}"#));
+    let script = fs.to_shell_script();
+    assert!(script.contains("mkdir -p \"$root\"/'a'/'e'\n"));
+    assert!(script.contains("truncate -s 584 \"$root\"/'a'/'e'/'i'\n"));
+
+    // Names from the session can't make the script write outside the root
+    assert_eq!(
+        parse("$ ls\ndir ..\n5 ../../escape").unwrap_err(),
+        ParseError::BadEntry { line: 2 }
+    );
+    let odd = parse("$ ls\ndir ...\n$ cd ...\n$ ls\n5 it's\n5 -rf\n5 .hidden").unwrap();
+    let script = odd.to_shell_script();
+    assert!(script.contains("mkdir -p \"$root\"/'...'\n"));
+    assert!(script.contains("truncate -s 5 \"$root\"/'...'/'it'\\''s'\n"));
+    for line in script.lines().filter(|line| line.contains("\"$root\"/")) {
+        let path = line.split("\"$root\"").nth(1).unwrap();
+        assert!(path.starts_with("/'"), "{line}");
+        assert!(!path.contains("/'..'") && !path.contains("/'.'"), "{line}");
+    }
+
+    // Recreate the tree on disk and read it back
+    let dir = std::env::temp_dir().join(format!("aoc_day07_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    for d in 1..fs.dirs.len() {
+        std::fs::create_dir_all(dir.join(&fs.path(d)[1..])).unwrap();
+    }
+    for (d, entries) in fs.dirs.iter().enumerate() {
+        for (name, entry) in &entries.entries {
+            if let Entry::File(size) = entry {
+                let path = dir.join(&fs.path(d)[1..]).join(name);
+                std::fs::File::create(path).unwrap().set_len(*size).unwrap();
+            }
+        }
+    }
+    let from_disk = FileSystem::from_dir(&dir);
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(from_disk.unwrap(), fs);
+}
+
+#[test]
+fn test_cleanup() {
+    let fs = parse(include_str!("../../inputs/day07_example1.txt")).unwrap();
+    assert_eq!(fs.space_to_free(70_000_000, 30_000_000), 8_381_165);
+    assert_eq!(
+        fs.plan_cleanup(70_000_000, 30_000_000),
+        Some(Plan {
+            paths: vec!["/d".to_string()],
+            freed: 24_933_642
+        })
+    );
+    assert_eq!(fs.plan_cleanup(40_000_000, 100_000_000), None);
+    // x and x/y are nested, so they can't both be picked
+    let small = parse(
+        "$ ls\ndir x\ndir z\n$ cd x\n$ ls\n40 a\ndir y\n$ cd y\n$ ls\n60 b\n$ cd /z\n$ ls\n50 c",
+    )
+    .unwrap();
+    assert_eq!(
+        small.plan_cleanup_set(150, 110, 1000),
+        Ok(Some(Plan {
+            paths: vec!["/x/y".to_string(), "/z".to_string()],
+            freed: 110
+        }))
+    );
+    assert_eq!(small.plan_cleanup(150, 110).unwrap().paths, ["/"]);
+    assert_eq!(small.plan_cleanup_set(150, 151, 1000), Ok(None));
+    assert_eq!(
+        fs.plan_cleanup_set(48_381_165, 1, 1000)
+            .unwrap()
+            .unwrap()
+            .paths,
+        ["/a/e"]
+    );
+    assert_eq!(
+        fs.plan_cleanup_set(48_381_165, 0, 1000),
+        Ok(Some(Plan {
+            paths: Vec::new(),
+            freed: 0
+        }))
+    );
+
+    // Sizes in GB, where the time and memory can't depend on the bytes
+    let gb = 1_000_000_000u64;
+    let mut big = FileSystem::new();
+    for (i, size) in [3, 5, 7, 11, 13].into_iter().enumerate() {
+        let dir = big.add_dir(ROOT, &format!("d{i}")).unwrap();
+        big.add_file(dir, "data", size * gb);
+        let inner = big.add_dir(dir, "inner").unwrap();
+        big.add_file(inner, "data", gb);
+    }
+    // 44 GB used: the directories hold 4, 6, 8, 12 and 14 GB, with 1 GB inside each
+    assert_eq!(big.space_to_free(60 * gb, 25 * gb), 9 * gb);
+    assert_eq!(big.plan_cleanup(60 * gb, 25 * gb).unwrap().paths, ["/d3"]);
+    // Several sets free exactly 9 GB
+    let plan = big
+        .plan_cleanup_set(60 * gb, 25 * gb, 1000)
+        .unwrap()
+        .unwrap();
+    assert_eq!(plan.freed, 9 * gb);
+    let plan = big
+        .plan_cleanup_set(50 * gb, 45 * gb, 1000)
+        .unwrap()
+        .unwrap();
+    assert_eq!(plan.freed, 39 * gb);
+    assert_eq!(
+        big.plan_cleanup_set(50 * gb, 45 * gb, 1)
+            .unwrap_err()
+            .best
+            .paths,
+        ["/"]
+    );
+}
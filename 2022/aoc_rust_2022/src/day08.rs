@@ -0,0 +1,299 @@
+//! Solutions to 2022: Advent of Code day 8
+//! By Peter Fornwall
+
+use crate::sniff::{self, Confidence};
+use itertools::Itertools;
+use rand::{rngs::StdRng, Rng};
+
+pub fn looks_like(input: &str) -> Confidence {
+    (sniff::all_lines(input, sniff::is_digits)
+        && sniff::same_width(input)
+        && sniff::sample(input).nth(1).is_some())
+    .into()
+}
+
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn parse(input: &str) -> Vec<Vec<i32>> {
+    let mut m: Vec<Vec<i32>> = Vec::new();
+    for line in input.trim().lines() {
+        m.push(line.as_bytes().iter().map(|x| (x - b'0') as i32).collect());
+    }
+    m
+}
+
+fn trees(input: &[Vec<i32>], mut x: i32, mut y: i32, dx: i32, dy: i32) -> (i32, bool) {
+    let height = input[y as usize][x as usize];
+    y += dy;
+    x += dx;
+    let mut c = 0;
+    let mut blocked = false;
+    while y >= 0 && y < (input.len() as i32) && x >= 0 && x < (input[0].len() as i32) {
+        c += 1;
+        if input[y as usize][x as usize] >= height {
+            blocked = true;
+            break;
+        }
+        y += dy;
+        x += dx;
+    }
+    (c, !blocked)
+}
+
+/// Reference for `part1`, walking out from every tree.
+pub fn part1_reference(input: &[Vec<i32>]) -> i32 {
+    let mut sum = 0;
+    for y in 0..input.len() as i32 {
+        for x in 0..input[0].len() as i32 {
+            let d = trees(input, x, y, 0, 1).1;
+            let u = trees(input, x, y, 0, -1).1;
+            let r = trees(input, x, y, 1, 0).1;
+            let l = trees(input, x, y, -1, 0).1;
+            if d || u || r || l {
+                sum += 1;
+            }
+        }
+    }
+    sum
+}
+
+/// Reference for `part2`, walking out from every tree.
+pub fn part2_reference(input: &[Vec<i32>]) -> i32 {
+    let mut max_c = 0;
+    for y in 0..input.len() {
+        for x in 0..input[0].len() {
+            let d = trees(input, x as i32, y as i32, 0, 1).0;
+            let u = trees(input, x as i32, y as i32, 0, -1).0;
+            let r = trees(input, x as i32, y as i32, 1, 0).0;
+            let l = trees(input, x as i32, y as i32, -1, 0).0;
+            let c = d * u * r * l;
+            if c > max_c {
+                max_c = c;
+            }
+        }
+    }
+    max_c
+}
+
+/// Visibility from outside the forest and scenic score of every tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Survey {
+    pub visible: Vec<Vec<bool>>,
+    pub scenic: Vec<Vec<i32>>,
+}
+
+/// Characters for ASCII shading, from lowest to highest.
+const SHADES: &[u8] = b" .:-=+*#%@";
+
+impl Survey {
+    /// Visibility mask as CSV, 1 for visible trees.
+    pub fn visible_csv(&self) -> String {
+        to_csv(&self.visible, |v| u8::from(*v))
+    }
+
+    pub fn scenic_csv(&self) -> String {
+        to_csv(&self.scenic, |s| *s)
+    }
+
+    /// Visibility mask with `#` for visible and `.` for hidden trees.
+    pub fn visible_ascii(&self) -> String {
+        self.visible
+            .iter()
+            .map(|row| row.iter().map(|v| if *v { '#' } else { '.' }))
+            .map(|row| row.chain(['\n']).collect::<String>())
+            .collect()
+    }
+
+    /// Scenic scores shaded from ` ` to `@`.
+    pub fn scenic_ascii(&self) -> String {
+        self.levels()
+            .iter()
+            .map(|row| row.iter().map(|l| level_char(*l)))
+            .map(|row| row.chain(['\n']).collect::<String>())
+            .collect()
+    }
+
+    /// Visibility mask as a binary PGM image, visible trees white.
+    pub fn visible_pgm(&self) -> Vec<u8> {
+        let pixels = self.visible.iter().flatten();
+        image(
+            b"P5",
+            &self.visible,
+            pixels.map(|v| if *v { 255 } else { 0 }),
+        )
+    }
+
+    /// Scenic scores as a binary PGM image, brighter for higher scores.
+    pub fn scenic_pgm(&self) -> Vec<u8> {
+        let levels = self.levels();
+        let pixels = levels.iter().flatten().map(|l| (l * 255.0).round() as u8);
+        image(b"P5", &self.scenic, pixels)
+    }
+
+    /// Scenic scores as a binary PPM heatmap, from black through red and
+    /// yellow to white.
+    pub fn scenic_ppm(&self) -> Vec<u8> {
+        let levels = self.levels();
+        let channel = |l: f64| (l.clamp(0.0, 1.0) * 255.0).round() as u8;
+        let pixels = levels.iter().flatten().flat_map(|l| {
+            [
+                channel(3.0 * l),
+                channel(3.0 * l - 1.0),
+                channel(3.0 * l - 2.0),
+            ]
+        });
+        image(b"P6", &self.scenic, pixels)
+    }
+
+    /// Scenic scores scaled logarithmically to 0.0..=1.0, as a few trees
+    /// score far higher than the rest.
+    fn levels(&self) -> Vec<Vec<f64>> {
+        let max = self.scenic.iter().flatten().copied().max().unwrap_or(0);
+        let scale = f64::from(max).ln_1p().max(f64::MIN_POSITIVE);
+        self.scenic
+            .iter()
+            .map(|row| row.iter().map(|s| f64::from(*s).ln_1p() / scale).collect())
+            .collect()
+    }
+}
+
+fn level_char(level: f64) -> char {
+    SHADES[(level * (SHADES.len() - 1) as f64).round() as usize] as char
+}
+
+fn to_csv<T, V: std::fmt::Display>(grid: &[Vec<T>], value: impl Fn(&T) -> V) -> String {
+    grid.iter()
+        .map(|row| row.iter().map(&value).join(",") + "\n")
+        .collect()
+}
+
+/// Netpbm image with the size of `grid` and one byte per channel.
+fn image<T>(magic: &[u8], grid: &[Vec<T>], pixels: impl Iterator<Item = u8>) -> Vec<u8> {
+    let width = grid.first().map_or(0, Vec::len);
+    let mut data = magic.to_vec();
+    data.extend(format!("\n{width} {}\n255\n", grid.len()).bytes());
+    data.extend(pixels);
+    data
+}
+
+/// Looks back from the `i`:th tree along a line. The stack holds the trees
+/// so far that are taller than all later ones, so every tree is pushed and
+/// popped once. Returns the viewing distance and if the tree is visible from
+/// the start of the line.
+fn look_back(stack: &mut Vec<(usize, i32)>, i: usize, height: i32) -> (i32, bool) {
+    while stack.last().is_some_and(|(_, h)| *h < height) {
+        stack.pop();
+    }
+    let seen = match stack.last() {
+        Some((blocker, _)) => ((i - blocker) as i32, false),
+        None => (i as i32, true),
+    };
+    stack.push((i, height));
+    seen
+}
+
+/// Surveys the forest looking along every row and column in both directions,
+/// O(n²) in total. Columns are done with one stack each, a row at a time.
+pub fn survey(input: &[Vec<i32>]) -> Survey {
+    let rows = input.len();
+    let cols = input.first().map_or(0, Vec::len);
+    let mut visible = vec![vec![false; cols]; rows];
+    let mut scenic = vec![vec![1; cols]; rows];
+    let mut see = |x: usize, y: usize, (distance, seen): (i32, bool)| {
+        visible[y][x] |= seen;
+        scenic[y][x] *= distance;
+    };
+    let mut stack = Vec::new();
+    for (y, row) in input.iter().enumerate() {
+        stack.clear();
+        for (x, height) in row.iter().enumerate() {
+            see(x, y, look_back(&mut stack, x, *height));
+        }
+        stack.clear();
+        for x in (0..cols).rev() {
+            see(x, y, look_back(&mut stack, cols - 1 - x, row[x]));
+        }
+    }
+    let mut stacks = vec![Vec::new(); cols];
+    for (y, row) in input.iter().enumerate() {
+        for x in 0..cols {
+            see(x, y, look_back(&mut stacks[x], y, row[x]));
+        }
+    }
+    stacks.iter_mut().for_each(Vec::clear);
+    for (y, row) in input.iter().enumerate().rev() {
+        for x in 0..cols {
+            see(x, y, look_back(&mut stacks[x], rows - 1 - y, row[x]));
+        }
+    }
+    Survey { visible, scenic }
+}
+
+pub fn part1(input: &[Vec<i32>]) -> i32 {
+    let survey = survey(input);
+    survey.visible.iter().flatten().filter(|v| **v).count() as i32
+}
+
+pub fn part2(input: &[Vec<i32>]) -> i32 {
+    let survey = survey(input);
+    survey.scenic.iter().flatten().copied().max().unwrap_or(0)
+}
+
+/// Random forest of tree heights 0 to 9.
+pub fn generate_forest(rng: &mut StdRng, width: usize, height: usize) -> String {
+    (0..height)
+        .map(|_| {
+            (0..width)
+                .map(|_| (b'0' + rng.gen_range(0..10)) as char)
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Small random forest, for cross-checking against the reference.
+pub fn generate(rng: &mut StdRng) -> String {
+    let width = rng.gen_range(1..=12);
+    let height = rng.gen_range(1..=12);
+    generate_forest(rng, width, height)
+}
+
+#[test]
+fn test1() {
+    let input = "30373
+25512
+65332
+33549
+35390";
+    let v = parse(input);
+
+    let result_p1 = part1(&v);
+    println!("Part1: {}", result_p1);
+    assert_eq!(result_p1, 21);
+    let result_p2 = part2(&v);
+    println!("Part2: {}", result_p2);
+    assert_eq!(result_p2, 8);
+    assert_eq!(part1_reference(&v), 21);
+    assert_eq!(part2_reference(&v), 8);
+    assert_eq!(survey(&v).scenic[3][2], 8);
+}
+
+#[test]
+fn test_survey_output() {
+    let survey = survey(&parse("30373\n25512\n65332\n33549\n35390"));
+    assert_eq!(
+        survey.visible_ascii(),
+        "#####\n###.#\n##.##\n#.#.#\n#####\n"
+    );
+    assert!(survey.visible_csv().starts_with("1,1,1,1,1\n1,1,1,0,1\n"));
+    assert_eq!(survey.scenic_csv().lines().nth(3), Some("0,1,8,3,0"));
+    assert_eq!(survey.scenic_ascii().lines().nth(3), Some(" -@* "));
+
+    let pgm = survey.scenic_pgm();
+    assert!(pgm.starts_with(b"P5\n5 5\n255\n"));
+    assert_eq!(pgm.len(), 11 + 25);
+    assert_eq!(pgm[11 + 3 * 5 + 2], 255);
+    let ppm = survey.scenic_ppm();
+    assert!(ppm.starts_with(b"P6\n5 5\n255\n"));
+    assert_eq!(ppm[11 + 3 * (3 * 5 + 2)..][..3], [255, 255, 255]);
+    assert_eq!(survey.visible_pgm()[11 + 5 + 3], 0);
+}
@@ -0,0 +1,306 @@
+//! Solutions to 2022: Advent of Code day 9
+//! By Peter Fornwall
+
+use crate::sniff::{self, Confidence};
+use std::{collections::HashSet, fmt, ops::RangeInclusive};
+
+pub type Pos = (i32, i32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Direction {
+    pub fn delta(self) -> Pos {
+        match self {
+            Direction::Up => (0, 1),
+            Direction::Down => (0, -1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+            Direction::UpLeft => (-1, 1),
+            Direction::UpRight => (1, 1),
+            Direction::DownLeft => (-1, -1),
+            Direction::DownRight => (1, -1),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Motion {
+    pub direction: Direction,
+    pub steps: usize,
+}
+
+/// Errors from parsing the motions, with 1-based line numbers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    BadDirection { line: usize, direction: String },
+    BadSteps { line: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::BadDirection { line, direction } => {
+                write!(f, "line {line}: unknown direction {direction:?}")
+            }
+            ParseError::BadSteps { line } => write!(f, "line {line}: bad number of steps"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Knots of a rope, the head first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rope {
+    pub knots: Vec<Pos>,
+}
+
+impl Rope {
+    /// Rope of `nr` knots, all at the start position (0, 0).
+    pub fn new(nr: usize) -> Self {
+        Rope::at(nr, (0, 0))
+    }
+
+    /// Rope of `nr` knots, all at `start`.
+    pub fn at(nr: usize, start: Pos) -> Self {
+        assert!(nr > 0, "a rope needs at least one knot");
+        Rope {
+            knots: vec![start; nr],
+        }
+    }
+
+    pub fn head(&self) -> Pos {
+        self.knots[0]
+    }
+
+    pub fn tail(&self) -> Pos {
+        self.knots[self.knots.len() - 1]
+    }
+
+    /// Moves the head one step, every other knot follows the one before it.
+    pub fn step(&mut self, direction: Direction) {
+        let (dx, dy) = direction.delta();
+        self.knots[0].0 += dx;
+        self.knots[0].1 += dy;
+        for i in 1..self.knots.len() {
+            let head = self.knots[i - 1];
+            let tail = &mut self.knots[i];
+            let is_not_touching = head.0.abs_diff(tail.0) > 1 || head.1.abs_diff(tail.1) > 1;
+            if !is_not_touching {
+                break;
+            }
+            tail.0 += (head.0 - tail.0).signum();
+            tail.1 += (head.1 - tail.1).signum();
+        }
+    }
+
+    /// Positions of all knots after every single step of the motions.
+    pub fn steps(mut self, motions: &[Motion]) -> impl Iterator<Item = Vec<Pos>> + '_ {
+        motions
+            .iter()
+            .flat_map(|motion| std::iter::repeat_n(motion.direction, motion.steps))
+            .map(move |direction| {
+                self.step(direction);
+                self.knots.clone()
+            })
+    }
+
+    /// Draws the rope and a trail, see `render`.
+    pub fn render(
+        &self,
+        trail: &HashSet<Pos>,
+        xs: RangeInclusive<i32>,
+        ys: RangeInclusive<i32>,
+    ) -> String {
+        render(&self.knots, trail, xs, ys)
+    }
+}
+
+/// Draws knots and a trail like in the puzzle description: `H` for the head,
+/// then `T` or the knot numbers, `s` for the start, and `#` for the trail.
+/// Rows go from the highest y down.
+pub fn render(
+    knots: &[Pos],
+    trail: &HashSet<Pos>,
+    xs: RangeInclusive<i32>,
+    ys: RangeInclusive<i32>,
+) -> String {
+    let mut s = String::new();
+    for y in ys.rev() {
+        for x in xs.clone() {
+            s.push(match knots.iter().position(|k| *k == (x, y)) {
+                Some(0) => 'H',
+                Some(_) if knots.len() == 2 => 'T',
+                Some(i) => char::from_digit(i as u32, 36).unwrap_or('?'),
+                None if (x, y) == (0, 0) => 's',
+                None if trail.contains(&(x, y)) => '#',
+                None => '.',
+            });
+        }
+        s.push('\n');
+    }
+    s
+}
+
+/// Positions visited by knot `knot` of a rope with `nr` knots, including the start.
+pub fn trail(motions: &[Motion], nr: usize, knot: usize) -> HashSet<Pos> {
+    let mut visited: HashSet<Pos> = Rope::new(nr)
+        .steps(motions)
+        .map(|knots| knots[knot])
+        .collect();
+    visited.insert((0, 0));
+    visited
+}
+
+/// Moves several independent ropes with the same motions. Returns the
+/// positions visited by the tail of each rope, including where it started.
+pub fn tail_trails(ropes: &[Rope], motions: &[Motion]) -> Vec<HashSet<Pos>> {
+    ropes
+        .iter()
+        .map(|rope| {
+            let start = rope.tail();
+            let steps = rope.clone().steps(motions);
+            let mut visited: HashSet<Pos> = steps.map(|knots| knots[knots.len() - 1]).collect();
+            visited.insert(start);
+            visited
+        })
+        .collect()
+}
+
+/// Positions visited by all the tails.
+pub fn overlap(trails: &[HashSet<Pos>]) -> HashSet<Pos> {
+    let Some((first, rest)) = trails.split_first() else {
+        return HashSet::new();
+    };
+    first
+        .iter()
+        .filter(|pos| rest.iter().all(|trail| trail.contains(pos)))
+        .copied()
+        .collect()
+}
+
+pub fn looks_like(input: &str) -> Confidence {
+    sniff::all_lines(input, |l| {
+        l.split_once(' ').is_some_and(|(direction, steps)| {
+            matches!(direction, "U" | "D" | "L" | "R" | "UL" | "UR" | "DL" | "DR")
+                && sniff::is_digits(steps)
+        })
+    })
+    .into()
+}
+
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn parse(input: &str) -> Result<Vec<Motion>, ParseError> {
+    let mut motions = Vec::new();
+    for (line_nr, line) in input.lines().enumerate() {
+        let line_nr = line_nr + 1;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let (direction, steps) = line.split_once(' ').unwrap_or((line, ""));
+        let direction = match direction {
+            "U" => Direction::Up,
+            "D" => Direction::Down,
+            "L" => Direction::Left,
+            "R" => Direction::Right,
+            "UL" => Direction::UpLeft,
+            "UR" => Direction::UpRight,
+            "DL" => Direction::DownLeft,
+            "DR" => Direction::DownRight,
+            _ => {
+                return Err(ParseError::BadDirection {
+                    line: line_nr,
+                    direction: direction.to_string(),
+                })
+            }
+        };
+        let steps = steps
+            .parse::<usize>()
+            .map_err(|_| ParseError::BadSteps { line: line_nr })?;
+        motions.push(Motion { direction, steps });
+    }
+    Ok(motions)
+}
+
+pub fn part1(input: &str) -> usize {
+    trail(&parse(input).unwrap(), 2, 1).len()
+}
+
+pub fn part2(input: &str) -> usize {
+    trail(&parse(input).unwrap(), 10, 9).len()
+}
+
+#[test]
+fn test1() {
+    let input = include_str!("../../inputs/day09_example1.txt");
+    let result_p1 = part1(input);
+    println!("Part1: {}", result_p1);
+    assert_eq!(result_p1, 13);
+    let result_p2 = part2(input);
+    println!("Part2: {}", result_p2);
+    assert_eq!(result_p2, 1);
+
+    let motions = parse(input).unwrap();
+    let knots = Rope::new(2).steps(&motions).last().unwrap();
+    assert_eq!(
+        Rope { knots }.render(&HashSet::new(), 0..=5, 0..=4),
+        "......\n......\n.TH...\n......\ns.....\n"
+    );
+    assert_eq!(
+        render(&[], &trail(&motions, 2, 1), 0..=5, 0..=4),
+        "..##..\n...##.\n.####.\n....#.\ns###..\n"
+    );
+}
+
+#[test]
+fn test2() {
+    let input = include_str!("../../inputs/day09_example2.txt");
+    let result_p2 = part2(input);
+    println!("Part2: {}", result_p2);
+    assert_eq!(result_p2, 36);
+
+    let motions = parse(input).unwrap();
+    let knots = Rope::new(10).steps(&motions[..1]).last().unwrap();
+    assert_eq!(
+        Rope { knots }
+            .render(&HashSet::new(), -11..=14, -5..=15)
+            .lines()
+            .nth(15),
+        Some("...........54321H.........")
+    );
+    assert_eq!(
+        parse("R 4\nX 1").unwrap_err(),
+        ParseError::BadDirection {
+            line: 2,
+            direction: "X".to_string()
+        }
+    );
+    assert_eq!(parse("R x").unwrap_err(), ParseError::BadSteps { line: 1 });
+}
+
+#[test]
+fn test_diagonals_and_several_ropes() {
+    let motions = parse("UR 3\nDR 1\nL 2").unwrap();
+    assert_eq!(motions[0].direction, Direction::UpRight);
+    let knots = Rope::new(3).steps(&motions).last().unwrap();
+    assert_eq!(knots, [(2, 2), (3, 2), (2, 2)]);
+
+    let motions = parse(include_str!("../../inputs/day09_example1.txt")).unwrap();
+    let ropes = [Rope::new(2), Rope::new(10), Rope::at(2, (1, 0))];
+    let trails = tail_trails(&ropes, &motions);
+    let visited: Vec<usize> = trails.iter().map(HashSet::len).collect();
+    assert_eq!(visited, [13, 1, 13]);
+    assert_eq!(overlap(&trails[..2]), HashSet::from([(0, 0)]));
+    assert_eq!(overlap(&[trails[0].clone(), trails[2].clone()]).len(), 8);
+}
@@ -0,0 +1,296 @@
+//! Solutions to 2022: Advent of Code day 10
+//! By Peter Fornwall
+
+use crate::{
+    bitmap::Bitmap,
+    sniff::{self, Confidence},
+};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Noop,
+    Addx(i32),
+}
+
+impl Instruction {
+    /// Number of cycles the instruction takes to complete.
+    pub fn cycles(self) -> usize {
+        match self {
+            Instruction::Noop => 1,
+            Instruction::Addx(_) => 2,
+        }
+    }
+
+    /// Updates the registers when the instruction completes.
+    pub fn execute(self, registers: &mut Registers) {
+        match self {
+            Instruction::Noop => {}
+            Instruction::Addx(v) => registers.x += v,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Registers {
+    pub x: i32,
+}
+
+impl Default for Registers {
+    fn default() -> Self {
+        Registers { x: 1 }
+    }
+}
+
+/// Errors from parsing the program, with 1-based line numbers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    UnknownInstruction { line: usize, name: String },
+    BadOperand { line: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::UnknownInstruction { line, name } => {
+                write!(f, "line {line}: unknown instruction {name:?}")
+            }
+            ParseError::BadOperand { line } => write!(f, "line {line}: bad operand"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Breakpoint {
+    /// Stop during this cycle, counting from 1
+    Cycle(usize),
+    /// Stop during the first cycle of the instruction at this index
+    Instruction(usize),
+}
+
+/// Emulator running a program one cycle at a time. As an iterator it yields
+/// the cycle number and the registers during every cycle, that is before the
+/// instruction finishing in that cycle has updated them.
+#[derive(Debug, Clone)]
+pub struct Cpu {
+    program: Vec<Instruction>,
+    registers: Registers,
+    /// Index of the current instruction
+    pc: usize,
+    /// Cycles spent so far on the current instruction
+    busy: usize,
+    /// Cycles completed
+    cycle: usize,
+    breakpoints: Vec<Breakpoint>,
+}
+
+impl Cpu {
+    pub fn new(program: Vec<Instruction>) -> Self {
+        Cpu {
+            program,
+            registers: Registers::default(),
+            pc: 0,
+            busy: 0,
+            cycle: 0,
+            breakpoints: Vec::new(),
+        }
+    }
+
+    pub fn registers(&self) -> Registers {
+        self.registers
+    }
+
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) {
+        self.breakpoints.push(breakpoint);
+    }
+
+    /// Runs until the next cycle with a breakpoint and returns it, like the
+    /// iterator. None if the program ends first.
+    pub fn run_to_breakpoint(&mut self) -> Option<(usize, Registers)> {
+        loop {
+            let cycle = self.cycle + 1;
+            let pc = self.pc;
+            let starting = self.busy == 0;
+            let hit = self.breakpoints.iter().any(|b| match b {
+                Breakpoint::Cycle(c) => *c == cycle,
+                Breakpoint::Instruction(i) => starting && *i == pc,
+            });
+            let state = self.next()?;
+            if hit {
+                return Some(state);
+            }
+        }
+    }
+}
+
+impl Iterator for Cpu {
+    type Item = (usize, Registers);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let instruction = *self.program.get(self.pc)?;
+        self.cycle += 1;
+        let during = self.registers;
+        tracing::trace!(
+            cycle = self.cycle,
+            pc = self.pc,
+            ?instruction,
+            x = during.x,
+            "cycle"
+        );
+        self.busy += 1;
+        if self.busy == instruction.cycles() {
+            instruction.execute(&mut self.registers);
+            self.pc += 1;
+            self.busy = 0;
+        }
+        Some((self.cycle, during))
+    }
+}
+
+/// Screen drawing one pixel per cycle, left to right and top to bottom, and
+/// starting over when it is full. A pixel is lit if the sprite, centered on
+/// the X register, covers it.
+#[derive(Debug, Clone)]
+pub struct Crt {
+    pub sprite_width: usize,
+    screen: Bitmap,
+}
+
+impl Crt {
+    pub fn new(width: usize, height: usize, sprite_width: usize) -> Self {
+        assert!(width > 0 && height > 0, "the screen needs pixels");
+        Crt {
+            sprite_width,
+            screen: Bitmap::new(width, height),
+        }
+    }
+
+    /// The 40 by 6 screen of the handheld device, with a 3 pixels wide sprite.
+    pub fn handheld() -> Self {
+        Crt::new(40, 6, 3)
+    }
+
+    /// Draws the pixel of a cycle, counting from 1.
+    pub fn draw(&mut self, cycle: usize, registers: Registers) {
+        let (width, height) = (self.screen.width(), self.screen.height());
+        let pixel = (cycle - 1) % (width * height);
+        let (x, y) = (pixel % width, pixel / width);
+        let left = registers.x - (self.sprite_width as i32 - 1) / 2;
+        let lit = (left..left + self.sprite_width as i32).contains(&(x as i32));
+        self.screen.set(x, y, lit);
+    }
+
+    /// Draws every cycle of the program.
+    pub fn run(&mut self, cpu: Cpu) {
+        for (cycle, registers) in cpu {
+            self.draw(cycle, registers);
+        }
+    }
+
+    pub fn screen(&self) -> &Bitmap {
+        &self.screen
+    }
+}
+
+pub fn looks_like(input: &str) -> Confidence {
+    sniff::all_lines(input, |l| {
+        l == "noop" || l.strip_prefix("addx ").is_some_and(sniff::is_signed)
+    })
+    .into()
+}
+
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn parse(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    let mut program = Vec::new();
+    for (line_nr, line) in input.lines().enumerate() {
+        let line_nr = line_nr + 1;
+        let words: Vec<_> = line.split_whitespace().collect();
+        let operand = |i: usize| {
+            words
+                .get(i)
+                .and_then(|w| w.parse::<i32>().ok())
+                .ok_or(ParseError::BadOperand { line: line_nr })
+        };
+        let instruction = match words.first() {
+            None => continue,
+            Some(&"noop") if words.len() == 1 => Instruction::Noop,
+            Some(&"addx") if words.len() == 2 => Instruction::Addx(operand(1)?),
+            Some(&"noop" | &"addx") => return Err(ParseError::BadOperand { line: line_nr }),
+            Some(name) => {
+                return Err(ParseError::UnknownInstruction {
+                    line: line_nr,
+                    name: name.to_string(),
+                })
+            }
+        };
+        program.push(instruction);
+    }
+    Ok(program)
+}
+
+pub fn part1(input: &str) -> i32 {
+    Cpu::new(parse(input).unwrap())
+        .filter(|(cycle, _)| *cycle >= 20 && (cycle - 20) % 40 == 0)
+        .map(|(cycle, registers)| cycle as i32 * registers.x)
+        .sum()
+}
+
+pub fn part2(input: &str) -> String {
+    let mut crt = Crt::handheld();
+    crt.run(Cpu::new(parse(input).unwrap()));
+    crt.screen().to_string()
+}
+
+#[test]
+fn test1() {
+    let input = include_str!("../../inputs/day10_example1.txt");
+    let result_p1 = part1(input);
+    println!("Part1: {}", result_p1);
+    assert_eq!(result_p1, 13140);
+    assert!(part2(input).starts_with("##..##..##..##..##..##..##..##..##..##..\n###...###...###"));
+}
+
+#[test]
+fn test_cpu() {
+    let program = parse("noop\naddx 3\naddx -5").unwrap();
+    let xs: Vec<_> = Cpu::new(program.clone()).map(|(_, r)| r.x).collect();
+    assert_eq!(xs, [1, 1, 1, 4, 4]);
+
+    let mut cpu = Cpu::new(program);
+    cpu.add_breakpoint(Breakpoint::Instruction(2));
+    cpu.add_breakpoint(Breakpoint::Cycle(5));
+    assert_eq!(cpu.run_to_breakpoint(), Some((4, Registers { x: 4 })));
+    assert_eq!(cpu.run_to_breakpoint(), Some((5, Registers { x: 4 })));
+    assert_eq!(cpu.run_to_breakpoint(), None);
+    assert_eq!(cpu.registers().x, -1);
+
+    assert_eq!(
+        parse("noop\nmul 2").unwrap_err(),
+        ParseError::UnknownInstruction {
+            line: 2,
+            name: "mul".to_string()
+        }
+    );
+    assert_eq!(
+        parse("addx").unwrap_err(),
+        ParseError::BadOperand { line: 1 }
+    );
+}
+
+#[test]
+fn test_crt() {
+    let program = parse("addx 1\naddx 2\nnoop\naddx 1").unwrap();
+    // X is 1, 1, 2, 2, 4, 4, 4 during the seven cycles, the last one wraps around
+    let mut crt = Crt::new(3, 2, 1);
+    crt.run(Cpu::new(program.clone()));
+    assert_eq!(crt.screen().to_string(), ".##\n...\n");
+    let mut crt = Crt::new(6, 1, 4);
+    crt.run(Cpu::new(program));
+    assert_eq!(crt.screen().to_text('X', ' '), " XXXXX\n");
+}
@@ -0,0 +1,875 @@
+//! Solutions to 2022: Advent of Code day 11
+//! By Peter Fornwall
+
+use crate::{bigint::BigInt, sniff::Confidence};
+use itertools::Itertools;
+use std::{fmt, mem};
+
+/// Step of a compiled operation, run on a stack.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Op {
+    Old,
+    Const(i64),
+    Add,
+    Sub,
+    Mul,
+}
+
+/// Operation over the old worry level, compiled to postfix order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Expr {
+    pub code: Vec<Op>,
+}
+
+impl Expr {
+    /// Compiles an expression of `old`, numbers, `+`, `-`, `*` and parentheses.
+    /// On error returns the 0-based byte offset of the problem.
+    pub fn compile(s: &str) -> Result<Expr, usize> {
+        let mut compiler = Compiler {
+            s: s.as_bytes(),
+            pos: 0,
+            code: Vec::new(),
+        };
+        compiler.expr()?;
+        compiler.skip_spaces();
+        if compiler.pos < s.len() {
+            return Err(compiler.pos);
+        }
+        Ok(Expr {
+            code: compiler.code,
+        })
+    }
+
+    /// Evaluates with plain `i64` arithmetic, panicking on overflow.
+    pub fn eval(&self, old: i64) -> i64 {
+        self.eval_worry(&old).expect("worry level overflow")
+    }
+
+    /// Evaluates with any worry representation, `None` on overflow.
+    pub fn eval_worry<W: Worry>(&self, old: &W) -> Option<W> {
+        let mut stack: Vec<W> = Vec::with_capacity(self.code.len());
+        for op in &self.code {
+            let value = match op {
+                Op::Old => old.clone(),
+                Op::Const(c) => W::from_i64(*c),
+                Op::Add | Op::Sub | Op::Mul => {
+                    let b = stack.pop().unwrap();
+                    let a = stack.pop().unwrap();
+                    match op {
+                        Op::Add => a.try_add(&b)?,
+                        Op::Sub => a.try_sub(&b)?,
+                        _ => a.try_mul(&b)?,
+                    }
+                }
+            };
+            stack.push(value);
+        }
+        stack.pop()
+    }
+
+    /// Range of results for `old` in `lo..=hi`, `None` if it can overflow.
+    pub fn bounds(&self, lo: i64, hi: i64) -> Option<(i64, i64)> {
+        let mut stack: Vec<(i64, i64)> = Vec::with_capacity(self.code.len());
+        for op in &self.code {
+            let value = match op {
+                Op::Old => (lo, hi),
+                Op::Const(c) => (*c, *c),
+                Op::Add | Op::Sub | Op::Mul => {
+                    let (b0, b1) = stack.pop().unwrap();
+                    let (a0, a1) = stack.pop().unwrap();
+                    match op {
+                        Op::Add => (a0.checked_add(b0)?, a1.checked_add(b1)?),
+                        Op::Sub => (a0.checked_sub(b1)?, a1.checked_sub(b0)?),
+                        _ => {
+                            let products = [
+                                a0.checked_mul(b0)?,
+                                a0.checked_mul(b1)?,
+                                a1.checked_mul(b0)?,
+                                a1.checked_mul(b1)?,
+                            ];
+                            (
+                                *products.iter().min().unwrap(),
+                                *products.iter().max().unwrap(),
+                            )
+                        }
+                    }
+                }
+            };
+            stack.push(value);
+        }
+        stack.pop()
+    }
+}
+
+/// Recursive descent over `expr = term (('+' | '-') term)*`,
+/// `term = factor ('*' factor)*` and `factor = old | number | '(' expr ')'`.
+struct Compiler<'a> {
+    s: &'a [u8],
+    pos: usize,
+    code: Vec<Op>,
+}
+
+impl Compiler<'_> {
+    fn skip_spaces(&mut self) {
+        while self.s.get(self.pos) == Some(&b' ') {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_spaces();
+        self.s.get(self.pos).copied()
+    }
+
+    fn expr(&mut self) -> Result<(), usize> {
+        self.term()?;
+        while let Some(c @ (b'+' | b'-')) = self.peek() {
+            self.pos += 1;
+            self.term()?;
+            self.code.push(if c == b'+' { Op::Add } else { Op::Sub });
+        }
+        Ok(())
+    }
+
+    fn term(&mut self) -> Result<(), usize> {
+        self.factor()?;
+        while self.peek() == Some(b'*') {
+            self.pos += 1;
+            self.factor()?;
+            self.code.push(Op::Mul);
+        }
+        Ok(())
+    }
+
+    fn factor(&mut self) -> Result<(), usize> {
+        let start = self.pos;
+        match self.peek() {
+            Some(b'(') => {
+                self.pos += 1;
+                self.expr()?;
+                if self.peek() != Some(b')') {
+                    return Err(self.pos);
+                }
+                self.pos += 1;
+            }
+            Some(b'0'..=b'9') => {
+                let start = self.pos;
+                while self.s.get(self.pos).is_some_and(u8::is_ascii_digit) {
+                    self.pos += 1;
+                }
+                let number = std::str::from_utf8(&self.s[start..self.pos]).unwrap();
+                self.code
+                    .push(Op::Const(number.parse().map_err(|_| start)?));
+            }
+            _ if self.s[self.pos..].starts_with(b"old") => {
+                self.pos += 3;
+                self.code.push(Op::Old);
+            }
+            _ => return Err(self.pos.max(start)),
+        }
+        Ok(())
+    }
+}
+
+/// Representation of worry levels, with arithmetic that reports overflow.
+pub trait Worry: Clone + fmt::Debug {
+    fn from_i64(value: i64) -> Self;
+    fn try_add(&self, other: &Self) -> Option<Self>;
+    fn try_sub(&self, other: &Self) -> Option<Self>;
+    fn try_mul(&self, other: &Self) -> Option<Self>;
+    /// Division rounding towards zero, as for `i64`
+    fn div_i64(&self, d: i64) -> Self;
+    /// Non-negative remainder
+    fn rem_i64(&self, d: i64) -> i64;
+}
+
+impl Worry for i64 {
+    fn from_i64(value: i64) -> Self {
+        value
+    }
+
+    fn try_add(&self, other: &Self) -> Option<Self> {
+        self.checked_add(*other)
+    }
+
+    fn try_sub(&self, other: &Self) -> Option<Self> {
+        self.checked_sub(*other)
+    }
+
+    fn try_mul(&self, other: &Self) -> Option<Self> {
+        self.checked_mul(*other)
+    }
+
+    fn div_i64(&self, d: i64) -> Self {
+        self / d
+    }
+
+    fn rem_i64(&self, d: i64) -> i64 {
+        self.rem_euclid(d)
+    }
+}
+
+impl Worry for BigInt {
+    fn from_i64(value: i64) -> Self {
+        BigInt::from(value)
+    }
+
+    fn try_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    fn try_sub(&self, other: &Self) -> Option<Self> {
+        Some(self - other)
+    }
+
+    fn try_mul(&self, other: &Self) -> Option<Self> {
+        Some(self * other)
+    }
+
+    fn div_i64(&self, d: i64) -> Self {
+        BigInt::div_i64(self, d)
+    }
+
+    fn rem_i64(&self, d: i64) -> i64 {
+        BigInt::rem_i64(self, d)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Monkey {
+    pub items: Vec<i64>,
+    pub op: Expr,
+    pub test_div: i64,
+    pub test_true_monkey: usize,
+    pub test_false_monkey: usize,
+}
+
+/// Errors from parsing the monkeys, with 1-based line and column numbers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    UnknownLine {
+        line: usize,
+    },
+    BadNumber {
+        line: usize,
+    },
+    BadExpression {
+        line: usize,
+        column: usize,
+    },
+    /// Monkeys must be numbered 0, 1, 2, ...
+    WrongMonkey {
+        line: usize,
+        expected: usize,
+    },
+    /// The monkey starting at `line` lacks a field
+    Missing {
+        line: usize,
+        field: &'static str,
+    },
+    UnknownTarget {
+        line: usize,
+        monkey: usize,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::UnknownLine { line } => write!(f, "line {line}: unknown line"),
+            ParseError::BadNumber { line } => write!(f, "line {line}: bad number"),
+            ParseError::BadExpression { line, column } => {
+                write!(f, "line {line}, column {column}: bad expression")
+            }
+            ParseError::WrongMonkey { line, expected } => {
+                write!(f, "line {line}: expected monkey {expected}")
+            }
+            ParseError::Missing { line, field } => {
+                write!(f, "line {line}: monkey without {field}")
+            }
+            ParseError::UnknownTarget { line, monkey } => {
+                write!(f, "line {line}: no monkey {monkey} to throw to")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Fields of a monkey while parsing, with the line of its header.
+#[derive(Default)]
+struct Fields {
+    line: usize,
+    items: Option<Vec<i64>>,
+    op: Option<Expr>,
+    test_div: Option<i64>,
+    /// Targets, with the lines they were given on
+    test_true_monkey: Option<(usize, usize)>,
+    test_false_monkey: Option<(usize, usize)>,
+}
+
+impl Fields {
+    fn monkey(self) -> Result<(Monkey, [(usize, usize); 2]), ParseError> {
+        let line = self.line;
+        let missing = |field| ParseError::Missing { line, field };
+        let targets = [
+            self.test_true_monkey.ok_or(missing("true target"))?,
+            self.test_false_monkey.ok_or(missing("false target"))?,
+        ];
+        let monkey = Monkey {
+            items: self.items.ok_or(missing("starting items"))?,
+            op: self.op.ok_or(missing("operation"))?,
+            test_div: self.test_div.ok_or(missing("test"))?,
+            test_true_monkey: targets[0].1,
+            test_false_monkey: targets[1].1,
+        };
+        Ok((monkey, targets))
+    }
+}
+
+pub fn looks_like(input: &str) -> Confidence {
+    input.trim_start().starts_with("Monkey 0:").into()
+}
+
+/// Parses the monkeys line by line from the keywords starting each line.
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn parse(input: &str) -> Result<Vec<Monkey>, ParseError> {
+    let mut monkeys = Vec::new();
+    let mut targets = Vec::new();
+    let mut fields: Option<Fields> = None;
+    for (line_nr, line) in input.lines().enumerate() {
+        let line_nr = line_nr + 1;
+        let indent = line.len() - line.trim_start().len();
+        let line = line.trim();
+        let number = |s: &str| {
+            s.trim()
+                .parse::<i64>()
+                .map_err(|_| ParseError::BadNumber { line: line_nr })
+        };
+        let target = |s: &str| {
+            let monkey = s
+                .trim()
+                .parse::<usize>()
+                .map_err(|_| ParseError::BadNumber { line: line_nr })?;
+            Ok::<_, ParseError>((line_nr, monkey))
+        };
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(id) = line
+            .strip_prefix("Monkey ")
+            .and_then(|l| l.strip_suffix(':'))
+        {
+            if let Some(done) = fields.take() {
+                let (monkey, monkey_targets) = done.monkey()?;
+                monkeys.push(monkey);
+                targets.extend(monkey_targets);
+            }
+            if id.parse::<usize>() != Ok(monkeys.len()) {
+                return Err(ParseError::WrongMonkey {
+                    line: line_nr,
+                    expected: monkeys.len(),
+                });
+            }
+            fields = Some(Fields {
+                line: line_nr,
+                ..Fields::default()
+            });
+            continue;
+        }
+        let Some(f) = fields.as_mut() else {
+            return Err(ParseError::UnknownLine { line: line_nr });
+        };
+        if let Some(items) = line.strip_prefix("Starting items:") {
+            let items = items.split(',').filter(|i| !i.trim().is_empty());
+            f.items = Some(items.map(number).collect::<Result<_, _>>()?);
+        } else if let Some(expr) = line.strip_prefix("Operation: new =") {
+            let offset = indent + "Operation: new =".len();
+            f.op = Some(
+                Expr::compile(expr).map_err(|pos| ParseError::BadExpression {
+                    line: line_nr,
+                    column: offset + pos + 1,
+                })?,
+            );
+        } else if let Some(div) = line.strip_prefix("Test: divisible by") {
+            let div = number(div)?;
+            if div <= 0 {
+                return Err(ParseError::BadNumber { line: line_nr });
+            }
+            f.test_div = Some(div);
+        } else if let Some(monkey) = line.strip_prefix("If true: throw to monkey") {
+            f.test_true_monkey = Some(target(monkey)?);
+        } else if let Some(monkey) = line.strip_prefix("If false: throw to monkey") {
+            f.test_false_monkey = Some(target(monkey)?);
+        } else {
+            return Err(ParseError::UnknownLine { line: line_nr });
+        }
+    }
+    if let Some(done) = fields {
+        let (monkey, monkey_targets) = done.monkey()?;
+        monkeys.push(monkey);
+        targets.extend(monkey_targets);
+    }
+    if let Some((line, monkey)) = targets.into_iter().find(|(_, m)| *m >= monkeys.len()) {
+        return Err(ParseError::UnknownTarget { line, monkey });
+    }
+    Ok(monkeys)
+}
+
+/// How worry levels are kept down after each inspection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Relief {
+    /// Divide by the amount, as when items are not damaged
+    Divide(i64),
+    /// Reduce modulo the least common multiple of the tests, see [`safe_modulus`]
+    Modulo,
+    /// No relief, only usable with [`BigInt`] for more than a few rounds
+    Exact,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorryError {
+    /// A worry level did not fit while `monkey` inspected in `round`
+    Overflow { round: usize, monkey: usize },
+    /// The least common multiple of the tests does not fit in an `i64`
+    ModulusOverflow,
+    /// The operation of `monkey` can overflow on worry levels below `modulus`
+    UnsafeModulus { monkey: usize, modulus: i64 },
+    /// The test of `monkey` is divisibility by 0 or less
+    BadTest { monkey: usize },
+    /// Relief dividing by 0
+    BadRelief,
+}
+
+impl fmt::Display for WorryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WorryError::Overflow { round, monkey } => {
+                write!(f, "round {round}: monkey {monkey} overflowed a worry level")
+            }
+            WorryError::ModulusOverflow => write!(f, "no modulus fits all tests"),
+            WorryError::UnsafeModulus { monkey, modulus } => {
+                write!(f, "monkey {monkey} can overflow below modulus {modulus}")
+            }
+            WorryError::BadTest { monkey } => {
+                write!(
+                    f,
+                    "monkey {monkey} tests divisibility by a non-positive number"
+                )
+            }
+            WorryError::BadRelief => write!(f, "relief can not divide by 0"),
+        }
+    }
+}
+
+impl std::error::Error for WorryError {}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+fn check_tests(monkeys: &[Monkey]) -> Result<(), WorryError> {
+    match monkeys.iter().position(|m| m.test_div <= 0) {
+        Some(monkey) => Err(WorryError::BadTest { monkey }),
+        None => Ok(()),
+    }
+}
+
+/// Least common multiple of the tests, checked so that no operation
+/// overflows on worry levels reduced by it.
+pub fn safe_modulus(monkeys: &[Monkey]) -> Result<i64, WorryError> {
+    check_tests(monkeys)?;
+    let modulus = monkeys.iter().try_fold(1i64, |acc, m| {
+        (acc / gcd(acc, m.test_div))
+            .checked_mul(m.test_div)
+            .ok_or(WorryError::ModulusOverflow)
+    })?;
+    for (monkey, m) in monkeys.iter().enumerate() {
+        if m.op.bounds(0, modulus - 1).is_none() {
+            return Err(WorryError::UnsafeModulus { monkey, modulus });
+        }
+    }
+    Ok(modulus)
+}
+
+/// Item held by a monkey, numbered in the order of the starting items.
+#[derive(Clone, Debug)]
+struct Item<W> {
+    id: usize,
+    worry: W,
+}
+
+/// Monkeys throwing items round by round.
+pub struct Simulation<'a, W> {
+    monkeys: &'a [Monkey],
+    relief: Relief,
+    modulus: i64,
+    round: usize,
+    items: Vec<Vec<Item<W>>>,
+    inspects: Vec<u64>,
+    /// Monkeys each item has been held by, if tracked
+    paths: Option<Vec<Vec<usize>>>,
+}
+
+/// State after a round, as in the round by round examples of the puzzle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Report<W> {
+    pub round: usize,
+    /// Worry levels of the items each monkey holds
+    pub items: Vec<Vec<W>>,
+    pub inspects: Vec<u64>,
+    /// Monkeys each item has been held by, starting with its first one
+    pub paths: Option<Vec<Vec<usize>>>,
+}
+
+impl<'a, W: Worry> Simulation<'a, W> {
+    /// With [`Relief::Modulo`] the starting items are reduced too, so that
+    /// no operation overflows.
+    pub fn new(monkeys: &'a [Monkey], relief: Relief) -> Result<Self, WorryError> {
+        check_tests(monkeys)?;
+        let modulus = match relief {
+            Relief::Modulo => safe_modulus(monkeys)?,
+            Relief::Divide(0) => return Err(WorryError::BadRelief),
+            _ => 0,
+        };
+        let reduce = |item: i64| match relief {
+            Relief::Modulo => item.rem_euclid(modulus),
+            _ => item,
+        };
+        let mut id = 0;
+        let items = monkeys
+            .iter()
+            .map(|m| {
+                m.items
+                    .iter()
+                    .map(|&item| {
+                        id += 1;
+                        Item {
+                            id: id - 1,
+                            worry: W::from_i64(reduce(item)),
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        Ok(Simulation {
+            monkeys,
+            relief,
+            modulus,
+            round: 0,
+            items,
+            inspects: vec![0; monkeys.len()],
+            paths: None,
+        })
+    }
+
+    /// Records the path of every item between the monkeys.
+    pub fn track_paths(mut self) -> Self {
+        let mut paths = Vec::new();
+        for (monkey, items) in self.items.iter().enumerate() {
+            paths.extend(items.iter().map(|_| vec![monkey]));
+        }
+        self.paths = Some(paths);
+        self
+    }
+
+    /// Lets every monkey take its turn once. On an error the round is not
+    /// done, and the simulation stays as it was before it.
+    pub fn step(&mut self) -> Result<(), WorryError> {
+        let round = self.round + 1;
+        let mut items = self.items.clone();
+        let mut inspects = self.inspects.clone();
+        // Throws as (item, monkey), added to the paths once the round is done
+        let mut throws = Vec::new();
+        for (monkey_index, monkey) in self.monkeys.iter().enumerate() {
+            for item in mem::take(&mut items[monkey_index]) {
+                inspects[monkey_index] += 1;
+                let worry = monkey
+                    .op
+                    .eval_worry(&item.worry)
+                    .ok_or(WorryError::Overflow {
+                        round,
+                        monkey: monkey_index,
+                    })?;
+                let worry = match self.relief {
+                    Relief::Divide(d) => worry.div_i64(d),
+                    Relief::Modulo => W::from_i64(worry.rem_i64(self.modulus)),
+                    Relief::Exact => worry,
+                };
+                let throw_to = if worry.rem_i64(monkey.test_div) == 0 {
+                    monkey.test_true_monkey
+                } else {
+                    monkey.test_false_monkey
+                };
+                if self.paths.is_some() {
+                    throws.push((item.id, throw_to));
+                }
+                items[throw_to].push(Item { id: item.id, worry });
+            }
+        }
+        if let Some(paths) = self.paths.as_mut() {
+            for (id, monkey) in throws {
+                paths[id].push(monkey);
+            }
+        }
+        self.round = round;
+        self.items = items;
+        self.inspects = inspects;
+        Ok(())
+    }
+
+    pub fn inspects(&self) -> &[u64] {
+        &self.inspects
+    }
+
+    pub fn report(&self) -> Report<W> {
+        Report {
+            round: self.round,
+            items: self
+                .items
+                .iter()
+                .map(|items| items.iter().map(|item| item.worry.clone()).collect())
+                .collect(),
+            inspects: self.inspects.clone(),
+            paths: self.paths.clone(),
+        }
+    }
+}
+
+impl<W: fmt::Display> Report<W> {
+    /// Items held, formatted as in the puzzle text.
+    pub fn holding(&self) -> String {
+        let mut s = format!(
+            "After round {}, the monkeys are holding items with these worry levels:\n",
+            self.round
+        );
+        for (monkey, items) in self.items.iter().enumerate() {
+            s += &format!("Monkey {monkey}: {}\n", items.iter().join(", "));
+        }
+        s
+    }
+
+    /// Inspection counts, formatted as in the puzzle text.
+    pub fn inspections(&self) -> String {
+        let mut s = format!("== After round {} ==\n", self.round);
+        for (monkey, inspects) in self.inspects.iter().enumerate() {
+            s += &format!("Monkey {monkey} inspected items {inspects} times.\n");
+        }
+        s
+    }
+}
+
+/// Runs the monkeys for `nr_rounds` and returns how many items each inspected.
+pub fn do_monkey_worries<W: Worry>(
+    monkeys: &[Monkey],
+    nr_rounds: usize,
+    relief: Relief,
+) -> Result<Vec<u64>, WorryError> {
+    let mut simulation = Simulation::<W>::new(monkeys, relief)?;
+    for _ in 0..nr_rounds {
+        simulation.step()?;
+    }
+    Ok(simulation.inspects().to_vec())
+}
+
+/// Product of the two largest inspection counts.
+pub fn monkey_business(inspects: &[u64]) -> u64 {
+    let mut inspects = inspects.to_vec();
+    inspects.sort_unstable_by(|a, b| b.cmp(a));
+    inspects[0] * inspects[1]
+}
+
+pub fn part1(monkeys: &[Monkey]) -> u64 {
+    monkey_business(&do_monkey_worries::<i64>(monkeys, 20, Relief::Divide(3)).unwrap())
+}
+
+pub fn part2(monkeys: &[Monkey]) -> u64 {
+    monkey_business(&do_monkey_worries::<i64>(monkeys, 10_000, Relief::Modulo).unwrap())
+}
+
+#[test]
+fn test1() {
+    let input = include_str!("../../inputs/day11_example1.txt");
+    let monkeys = parse(input).unwrap();
+    let result_p1 = part1(&monkeys);
+    println!("Part1: {}", result_p1);
+    assert_eq!(result_p1, 10605);
+    let result_p2 = part2(&monkeys);
+    println!("Part2: {}", result_p2);
+    assert_eq!(result_p2, 2713310158);
+}
+
+#[test]
+fn test_parse() {
+    let expr = Expr::compile("old * 3 + 2").unwrap();
+    assert_eq!(expr.eval(5), 17);
+    assert_eq!(Expr::compile("old + old").unwrap().eval(5), 10);
+    assert_eq!(Expr::compile(" (old - 1) * (2 + old)").unwrap().eval(5), 28);
+    assert_eq!(Expr::compile("old * * 2"), Err(6));
+    assert_eq!(Expr::compile("(old"), Err(4));
+    assert_eq!(Expr::compile("old 2"), Err(4));
+
+    // More than 10 monkeys, each throwing to the next one
+    let input: String = (0..12)
+        .map(|i| {
+            format!(
+                "Monkey {i}:\n  Starting items: {i}\n  Operation: new = old * old\n  Test: divisible by 7\n    If true: throw to monkey {}\n    If false: throw to monkey {}\n\n",
+                (i + 1) % 12,
+                (i + 11) % 12,
+            )
+        })
+        .collect();
+    let monkeys = parse(&input).unwrap();
+    assert_eq!(monkeys.len(), 12);
+    assert_eq!(monkeys[10].test_true_monkey, 11);
+    assert_eq!(monkeys[11].test_false_monkey, 10);
+
+    let bad = |input: &str| parse(input).unwrap_err();
+    assert_eq!(
+        bad("Monkey 0:\n  Operation: new = old +"),
+        ParseError::BadExpression {
+            line: 2,
+            column: 25
+        }
+    );
+    assert_eq!(
+        bad("Monkey 1:"),
+        ParseError::WrongMonkey {
+            line: 1,
+            expected: 0
+        }
+    );
+    assert_eq!(
+        bad("Monkey 0:\n  Starting items: 1\n"),
+        ParseError::Missing {
+            line: 1,
+            field: "true target"
+        }
+    );
+    assert_eq!(
+        bad(&input.replace("monkey 11", "monkey 12")),
+        ParseError::UnknownTarget {
+            line: 6,
+            monkey: 12
+        }
+    );
+    assert_eq!(
+        bad("Monkey 0:\n  Starting items: 1, x"),
+        ParseError::BadNumber { line: 2 }
+    );
+}
+
+#[test]
+fn test_worry() {
+    let input = include_str!("../../inputs/day11_example1.txt");
+    let monkeys = parse(input).unwrap();
+    assert_eq!(safe_modulus(&monkeys), Ok(96577));
+    let exact = do_monkey_worries::<BigInt>(&monkeys, 20, Relief::Exact).unwrap();
+    assert_eq!(exact, vec![99, 97, 8, 103]);
+    assert_eq!(
+        do_monkey_worries::<i64>(&monkeys, 20, Relief::Modulo),
+        Ok(exact)
+    );
+    assert!(matches!(
+        do_monkey_worries::<i64>(&monkeys, 20, Relief::Exact),
+        Err(WorryError::Overflow { .. })
+    ));
+
+    let mut unsafe_monkeys = monkeys.clone();
+    unsafe_monkeys[2].op = Expr::compile("old * old * old * old").unwrap();
+    assert_eq!(
+        safe_modulus(&unsafe_monkeys),
+        Err(WorryError::UnsafeModulus {
+            monkey: 2,
+            modulus: 96577
+        })
+    );
+    for (m, div) in unsafe_monkeys
+        .iter_mut()
+        .zip([1_000_000_007, 1_000_000_009, 998_244_353, 3])
+    {
+        m.test_div = div;
+    }
+    assert_eq!(
+        safe_modulus(&unsafe_monkeys),
+        Err(WorryError::ModulusOverflow)
+    );
+
+    // Starting items above the modulus are reduced before the first operation
+    let mut large_items = monkeys.clone();
+    large_items[2].items = vec![i64::MAX - 1];
+    assert_eq!(
+        do_monkey_worries::<i64>(&large_items, 20, Relief::Modulo).unwrap(),
+        do_monkey_worries::<BigInt>(&large_items, 20, Relief::Exact).unwrap()
+    );
+
+    assert!(matches!(
+        Simulation::<i64>::new(&monkeys, Relief::Divide(0)),
+        Err(WorryError::BadRelief)
+    ));
+    let mut zero_test = monkeys.clone();
+    zero_test[1].test_div = 0;
+    assert_eq!(
+        safe_modulus(&zero_test),
+        Err(WorryError::BadTest { monkey: 1 })
+    );
+    assert!(matches!(
+        Simulation::<i64>::new(&zero_test, Relief::Divide(3)),
+        Err(WorryError::BadTest { monkey: 1 })
+    ));
+}
+
+#[test]
+fn test_report() {
+    let input = include_str!("../../inputs/day11_example1.txt");
+    let monkeys = parse(input).unwrap();
+    let mut simulation = Simulation::<i64>::new(&monkeys, Relief::Divide(3))
+        .unwrap()
+        .track_paths();
+    simulation.step().unwrap();
+    let report = simulation.report();
+    assert_eq!(
+        report.holding(),
+        "After round 1, the monkeys are holding items with these worry levels:
+Monkey 0: 20, 23, 27, 26
+Monkey 1: 2080, 25, 167, 207, 401, 1046
+Monkey 2: \nMonkey 3: \n"
+    );
+    // Item 79 goes 0 -> 3 -> 1 in the first round, item 54 goes 1 -> 0
+    let paths = report.paths.unwrap();
+    assert_eq!(paths[0], vec![0, 3, 1]);
+    assert_eq!(paths[2], vec![1, 0]);
+
+    let mut simulation = Simulation::<i64>::new(&monkeys, Relief::Modulo).unwrap();
+    simulation.step().unwrap();
+    assert_eq!(
+        simulation.report().inspections(),
+        "== After round 1 ==
+Monkey 0 inspected items 2 times.
+Monkey 1 inspected items 4 times.
+Monkey 2 inspected items 3 times.
+Monkey 3 inspected items 6 times.
+"
+    );
+    assert_eq!(simulation.report().paths, None);
+
+    // A failed round leaves the simulation as it was
+    let mut overflowing = monkeys.clone();
+    overflowing[3].op = Expr::compile("old * 10000000000000000").unwrap();
+    let mut simulation = Simulation::<i64>::new(&overflowing, Relief::Exact)
+        .unwrap()
+        .track_paths();
+    let before = simulation.report();
+    assert_eq!(
+        simulation.step(),
+        Err(WorryError::Overflow {
+            round: 1,
+            monkey: 3
+        })
+    );
+    assert_eq!(simulation.report(), before);
+}
@@ -0,0 +1,339 @@
+//! Solutions to 2022: Advent of Code day 12
+//! By Peter Fornwall
+
+use crate::sniff::{self, Confidence};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+};
+
+pub struct Trees {
+    pub map: Vec<Vec<i32>>,
+    pub start: (i32, i32),
+    pub end: (i32, i32),
+}
+
+pub fn looks_like(input: &str) -> Confidence {
+    (sniff::all_lines(input, |l| {
+        l.bytes()
+            .all(|c| c.is_ascii_lowercase() || c == b'S' || c == b'E')
+    }) && sniff::same_width(input)
+        && input.contains('S')
+        && input.contains('E'))
+    .into()
+}
+
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn parse(input: &str) -> Trees {
+    let mut start = (0i32, 0i32);
+    let mut end = (0i32, 0i32);
+    let map = input
+        .trim()
+        .lines()
+        .enumerate()
+        .map(|(y, line)| {
+            line.trim()
+                .chars()
+                .enumerate()
+                .map(|(x, c)| {
+                    if c == 'S' {
+                        start = (x as i32, y as i32);
+                        0
+                    } else if c == 'E' {
+                        end = (x as i32, y as i32);
+                        (b'z' - b'a') as i32
+                    } else {
+                        c as i32 - 'a' as i32
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    Trees { map, start, end }
+}
+
+/// Which moves between neighbouring squares are allowed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rules {
+    pub max_climb: i32,
+    /// No limit on descents if `None`
+    pub max_descent: Option<i32>,
+    pub diagonals: bool,
+}
+
+impl Default for Rules {
+    /// The rules of the puzzle: climb at most one, descend any amount.
+    fn default() -> Self {
+        Rules {
+            max_climb: 1,
+            max_descent: None,
+            diagonals: false,
+        }
+    }
+}
+
+impl Rules {
+    pub fn allows(&self, from_height: i32, to_height: i32) -> bool {
+        to_height - from_height <= self.max_climb
+            && self
+                .max_descent
+                .is_none_or(|descent| from_height - to_height <= descent)
+    }
+
+    fn moves(&self) -> &'static [(i32, i32)] {
+        const MOVES: [(i32, i32); 8] = [
+            (1, 0),
+            (-1, 0),
+            (0, 1),
+            (0, -1),
+            (1, 1),
+            (-1, 1),
+            (1, -1),
+            (-1, -1),
+        ];
+        if self.diagonals {
+            &MOVES
+        } else {
+            &MOVES[..4]
+        }
+    }
+}
+
+/// Cost of a move, charged per step and per unit of climb or descent.
+/// Unsigned, as Dijkstra's search is wrong with negative costs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Costs {
+    pub step: u32,
+    pub climb: u32,
+    pub descent: u32,
+}
+
+impl Costs {
+    /// Cost of a move, at most `i32::MAX`.
+    pub fn cost(&self, from_height: i32, to_height: i32) -> i32 {
+        let change = to_height as i64 - from_height as i64;
+        let cost = self.step as i64
+            + self.climb as i64 * change.max(0)
+            + self.descent as i64 * (-change).max(0);
+        i32::try_from(cost).unwrap_or(i32::MAX)
+    }
+}
+
+/// Shortest number of steps from every square to the end, with the next
+/// square to go to. Found by one search backwards from the end.
+/// With a cost model, `steps` holds the lowest total cost instead.
+pub struct Distances {
+    pub steps: Vec<Vec<Option<i32>>>,
+    pub next: Vec<Vec<Option<(i32, i32)>>>,
+}
+
+impl Distances {
+    pub fn steps(&self, pos: (i32, i32)) -> Option<i32> {
+        self.steps[pos.1 as usize][pos.0 as usize]
+    }
+
+    /// Squares of a shortest path from `pos` to the end, both included.
+    pub fn path(&self, mut pos: (i32, i32)) -> Option<Vec<(i32, i32)>> {
+        self.steps(pos)?;
+        let mut path = vec![pos];
+        while let Some(next) = self.next[pos.1 as usize][pos.0 as usize] {
+            path.push(next);
+            pos = next;
+        }
+        Some(path)
+    }
+}
+
+pub fn distances_to_end(trees: &Trees) -> Distances {
+    distances_to_end_with(trees, &Rules::default(), None)
+}
+
+/// Breadth first search under `rules`, or Dijkstra when moves have `costs`.
+pub fn distances_to_end_with(trees: &Trees, rules: &Rules, costs: Option<&Costs>) -> Distances {
+    let m = &trees.map;
+    let mut steps = vec![vec![None; m[0].len()]; m.len()];
+    let mut next = vec![vec![None; m[0].len()]; m.len()];
+    // Breadth first search keeps the queue in order; Dijkstra needs the heap
+    let mut queue = VecDeque::new();
+    let mut heap = BinaryHeap::new();
+    steps[trees.end.1 as usize][trees.end.0 as usize] = Some(0);
+    queue.push_back(trees.end);
+    heap.push(Reverse((0, trees.end)));
+    loop {
+        let pos = if costs.is_some() {
+            match heap.pop() {
+                Some(Reverse((cost, pos)))
+                    if steps[pos.1 as usize][pos.0 as usize] == Some(cost) =>
+                {
+                    pos
+                }
+                Some(_) => continue,
+                None => break,
+            }
+        } else {
+            match queue.pop_front() {
+                Some(pos) => pos,
+                None => break,
+            }
+        };
+        let height = m[pos.1 as usize][pos.0 as usize];
+        let nr_steps: i32 = steps[pos.1 as usize][pos.0 as usize].unwrap();
+        for diff_pos in rules.moves() {
+            let from = (pos.0 + diff_pos.0, pos.1 + diff_pos.1);
+            if from.0 < 0 || from.0 >= m[0].len() as i32 || from.1 < 0 || from.1 >= m.len() as i32 {
+                continue;
+            }
+            let (x, y) = (from.0 as usize, from.1 as usize);
+            // Searching backwards, so the move is from `from` to `pos`
+            if !rules.allows(m[y][x], height) {
+                continue;
+            }
+            let new_steps = nr_steps.saturating_add(costs.map_or(1, |c| c.cost(m[y][x], height)));
+            if steps[y][x].is_none_or(|old| new_steps < old) {
+                steps[y][x] = Some(new_steps);
+                next[y][x] = Some(pos);
+                if costs.is_some() {
+                    heap.push(Reverse((new_steps, from)));
+                } else {
+                    queue.push_back(from);
+                }
+            }
+        }
+    }
+    Distances { steps, next }
+}
+
+/// Lowest square closest to the end, if any can reach it.
+pub fn best_start(trees: &Trees, distances: &Distances) -> Option<(i32, i32)> {
+    let m = &trees.map;
+    (0..m.len())
+        .flat_map(|y| (0..m[0].len()).map(move |x| (x as i32, y as i32)))
+        .filter(|&(x, y)| m[y as usize][x as usize] == 0)
+        .filter(|&pos| distances.steps(pos).is_some())
+        .min_by_key(|&pos| distances.steps(pos))
+}
+
+/// Draws the path on an empty map with arrows, as in the puzzle.
+/// Diagonal moves are drawn with arrows such as `↗`.
+pub fn render_path(trees: &Trees, path: &[(i32, i32)]) -> String {
+    let mut rows = vec![vec!['.'; trees.map[0].len()]; trees.map.len()];
+    rows[trees.end.1 as usize][trees.end.0 as usize] = 'E';
+    for (from, to) in path.iter().zip(path.iter().skip(1)) {
+        rows[from.1 as usize][from.0 as usize] = match (to.0 - from.0, to.1 - from.1) {
+            (1, 0) => '>',
+            (-1, 0) => '<',
+            (0, 1) => 'v',
+            (0, -1) => '^',
+            (1, 1) => '↘',
+            (-1, 1) => '↙',
+            (1, -1) => '↗',
+            _ => '↖',
+        };
+    }
+    rows.into_iter()
+        .map(|row| row.into_iter().chain(['\n']).collect::<String>())
+        .collect()
+}
+
+pub fn part1(input: &str) -> i32 {
+    let trees = parse(input);
+    distances_to_end(&trees).steps(trees.start).unwrap()
+}
+
+pub fn part2(input: &str) -> i32 {
+    let trees = parse(input);
+    let distances = distances_to_end(&trees);
+    distances
+        .steps(best_start(&trees, &distances).unwrap())
+        .unwrap()
+}
+
+#[test]
+fn test1() {
+    let input = include_str!("../../inputs/day12_example1.txt");
+
+    let result_p1 = part1(input);
+    println!("Part1: {}", result_p1);
+    assert_eq!(result_p1, 31);
+    let result_p2 = part2(input);
+    println!("Part2: {}", result_p2);
+    assert_eq!(result_p2, 29);
+}
+
+#[test]
+fn test_path() {
+    let trees = parse(include_str!("../../inputs/day12_example1.txt"));
+    let distances = distances_to_end(&trees);
+    let path = distances.path(trees.start).unwrap();
+    assert_eq!(path.len(), 32);
+    assert_eq!(path.last(), Some(&trees.end));
+    // Another of the shortest paths than the one drawn in the puzzle
+    assert_eq!(
+        render_path(&trees, &path),
+        "v..v<<<<
+>v.vv<<^
+.v.v>E^^
+.>v>>>^^
+..>>>>>^
+"
+    );
+    let start = best_start(&trees, &distances).unwrap();
+    assert_eq!(distances.path(start).unwrap().len(), 30);
+}
+
+#[test]
+fn test_rules() {
+    // A single peak in the middle of the top row
+    let trees = Trees {
+        map: vec![vec![0, 5, 0], vec![0, 0, 0]],
+        start: (0, 0),
+        end: (2, 0),
+    };
+    let steep = Rules {
+        max_climb: 5,
+        ..Rules::default()
+    };
+    let steps = |rules: &Rules, costs: Option<&Costs>| {
+        let distances = distances_to_end_with(&trees, rules, costs);
+        let path = distances.path(trees.start);
+        (distances.steps(trees.start), path.map(|p| p.len()))
+    };
+    assert_eq!(steps(&Rules::default(), None), (Some(4), Some(5)));
+    assert_eq!(steps(&steep, None), (Some(2), Some(3)));
+    let careful = Rules {
+        max_descent: Some(0),
+        ..steep
+    };
+    assert_eq!(steps(&careful, None), (Some(4), Some(5)));
+    let diagonal = Rules {
+        diagonals: true,
+        ..Rules::default()
+    };
+    let distances = distances_to_end_with(&trees, &diagonal, None);
+    let path = distances.path(trees.start).unwrap();
+    assert_eq!(render_path(&trees, &path), "↘.E\n.↗.\n");
+
+    // Over the peak costs 2 + 5 + 5, around it costs 4
+    let costs = Costs {
+        step: 1,
+        climb: 1,
+        descent: 1,
+    };
+    assert_eq!(steps(&steep, Some(&costs)), (Some(4), Some(5)));
+    let flat = Costs {
+        step: 10,
+        climb: 1,
+        descent: 0,
+    };
+    assert_eq!(steps(&steep, Some(&flat)), (Some(25), Some(3)));
+
+    let input = include_str!("../../inputs/day12_example1.txt");
+    let trees = parse(input);
+    let unit = Costs {
+        step: 1,
+        climb: 0,
+        descent: 0,
+    };
+    let dijkstra = distances_to_end_with(&trees, &Rules::default(), Some(&unit));
+    assert_eq!(dijkstra.steps(trees.start), Some(31));
+}
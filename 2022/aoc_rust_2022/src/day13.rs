@@ -0,0 +1,361 @@
+//! Solutions to 2022: Advent of Code day 13
+//! By Peter Fornwall
+
+use crate::sniff::{self, Confidence};
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+/// Packet or part of one. Equality is structural, wrap it in a [`Packet`]
+/// to compare it in the order the packets should be in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Item {
+    Number(u64),
+    List(Vec<Item>),
+}
+
+/// Item compared in packet order, where a number compared to a list is
+/// treated as a list of just that number. So `2` and `[[2]]` are equal.
+#[derive(Debug, Clone)]
+pub struct Packet(pub Item);
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        item_cmp(&self.0, &other.0)
+    }
+}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Packet {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Packet {}
+
+fn item_cmp(item1: &Item, item2: &Item) -> Ordering {
+    match (item1, item2) {
+        (Item::Number(nr1), Item::Number(nr2)) => nr1.cmp(nr2),
+        (Item::List(list1), Item::List(list2)) => list_cmp(list1, list2),
+        (Item::Number(_), Item::List(list2)) => list_cmp(std::slice::from_ref(item1), list2),
+        (Item::List(list1), Item::Number(_)) => list_cmp(list1, std::slice::from_ref(item2)),
+    }
+}
+
+fn list_cmp(list1: &[Item], list2: &[Item]) -> Ordering {
+    list1
+        .iter()
+        .zip(list2)
+        .map(|(i1, i2)| item_cmp(i1, i2))
+        .find(|c| *c != Ordering::Equal)
+        .unwrap_or_else(|| list1.len().cmp(&list2.len()))
+}
+
+impl fmt::Display for Item {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Item::Number(nr) => write!(f, "{nr}"),
+            Item::List(list) => {
+                write!(f, "[")?;
+                for (i, item) in list.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+/// Errors from parsing a single item, with 1-based column numbers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ItemError {
+    Unexpected {
+        column: usize,
+        found: char,
+    },
+    UnexpectedEnd {
+        column: usize,
+    },
+    /// The number starting at `column` does not fit in a `u64`
+    Overflow {
+        column: usize,
+    },
+}
+
+impl fmt::Display for ItemError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ItemError::Unexpected { column, found } => {
+                write!(f, "column {column}: unexpected '{found}'")
+            }
+            ItemError::UnexpectedEnd { column } => write!(f, "column {column}: unexpected end"),
+            ItemError::Overflow { column } => write!(f, "column {column}: number too large"),
+        }
+    }
+}
+
+impl std::error::Error for ItemError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    Item {
+        line: usize,
+        error: ItemError,
+    },
+    /// The pair starting at `line` does not have two packets
+    BadPair {
+        line: usize,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Item { line, error } => write!(f, "line {line}, {error}"),
+            ParseError::BadPair { line } => write!(f, "line {line}: not a pair of packets"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl FromStr for Packet {
+    type Err = ItemError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Packet)
+    }
+}
+
+impl FromStr for Item {
+    type Err = ItemError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            text: s,
+            s: s.as_bytes(),
+            pos: 0,
+        };
+        let item = parser.item()?;
+        match parser.s.get(parser.pos) {
+            None => Ok(item),
+            Some(_) => Err(parser.unexpected()),
+        }
+    }
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    s: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    /// Error at the current position, which is always at the start of a
+    /// character as only ASCII is consumed.
+    fn unexpected(&self) -> ItemError {
+        match self.text[self.pos..].chars().next() {
+            Some(found) => ItemError::Unexpected {
+                column: self.text[..self.pos].chars().count() + 1,
+                found,
+            },
+            None => ItemError::UnexpectedEnd {
+                column: self.pos + 1,
+            },
+        }
+    }
+
+    fn item(&mut self) -> Result<Item, ItemError> {
+        match self.s.get(self.pos) {
+            Some(b'[') => {
+                self.pos += 1;
+                let mut list = Vec::new();
+                if self.s.get(self.pos) == Some(&b']') {
+                    self.pos += 1;
+                    return Ok(Item::List(list));
+                }
+                loop {
+                    list.push(self.item()?);
+                    match self.s.get(self.pos) {
+                        Some(b',') => self.pos += 1,
+                        Some(b']') => {
+                            self.pos += 1;
+                            return Ok(Item::List(list));
+                        }
+                        _ => return Err(self.unexpected()),
+                    }
+                }
+            }
+            Some(c) if c.is_ascii_digit() => {
+                let column = self.pos + 1;
+                let mut nr: u64 = 0;
+                while let Some(c) = self.s.get(self.pos).filter(|c| c.is_ascii_digit()) {
+                    nr = nr
+                        .checked_mul(10)
+                        .and_then(|nr| nr.checked_add((c - b'0') as u64))
+                        .ok_or(ItemError::Overflow { column })?;
+                    self.pos += 1;
+                }
+                Ok(Item::Number(nr))
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+}
+
+pub fn looks_like(input: &str) -> Confidence {
+    sniff::all_lines(input, |l| {
+        l.is_empty() || (l.starts_with('[') && l.ends_with(']'))
+    })
+    .into()
+}
+
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn parse(input: &str) -> Result<Vec<(Packet, Packet)>, ParseError> {
+    let mut pairs = Vec::new();
+    let mut group: Vec<(usize, Packet)> = Vec::new();
+    // An empty line at the end closes the last pair
+    for (line_nr, line) in input.lines().chain([""]).enumerate() {
+        let line_nr = line_nr + 1;
+        let line = line.trim();
+        if !line.is_empty() {
+            let item = line.parse().map_err(|error| ParseError::Item {
+                line: line_nr,
+                error,
+            })?;
+            group.push((line_nr, item));
+            continue;
+        }
+        match group.len() {
+            0 => {}
+            2 => {
+                let second = group.pop().unwrap().1;
+                pairs.push((group.pop().unwrap().1, second));
+            }
+            _ => return Err(ParseError::BadPair { line: group[0].0 }),
+        }
+    }
+    Ok(pairs)
+}
+
+pub fn part1(input: &str) -> i32 {
+    let mut sum: i32 = 0;
+
+    let m = parse(input).unwrap();
+    for (ind, (i1, i2)) in m.iter().enumerate() {
+        if i1 <= i2 {
+            sum += ind as i32 + 1;
+        }
+    }
+    sum
+}
+
+pub fn part2(input: &str) -> usize {
+    let mut items: Vec<Packet> = parse(input)
+        .unwrap()
+        .into_iter()
+        .flat_map(|(i1, i2)| [i1, i2])
+        .collect();
+    items.sort();
+    // The dividers go first among any packets that are equal to them
+    let key1: Packet = "[[2]]".parse().unwrap();
+    let key2: Packet = "[[6]]".parse().unwrap();
+    let before = |key: &Packet| items.partition_point(|item| item < key);
+    let pos1 = before(&key1) + 1;
+    let pos2 = before(&key2) + 2;
+    pos1 * pos2
+}
+
+#[test]
+fn test1() {
+    let input = include_str!("../../inputs/day13_example1.txt");
+
+    let result_p1 = part1(input);
+    println!("Part1: {}", result_p1);
+    assert_eq!(result_p1, 13);
+    let result_p2 = part2(input);
+    println!("Part2: {}", result_p2);
+    assert_eq!(result_p2, 140);
+}
+
+#[test]
+fn test_item() {
+    let text = "[1,[2,[3,[4,[5,6,7]]]],8,9,[],18446744073709551615]";
+    let item: Item = text.parse().unwrap();
+    assert_eq!(item.to_string(), text);
+    let packet = |s: &str| s.parse::<Packet>().unwrap();
+    let equal = (packet("[[1],[2,3,4]]"), packet("[1,[2,3,4]]"));
+    assert_ne!(equal.0 .0, equal.1 .0);
+    assert_eq!(equal.0, equal.1);
+    assert_ne!(packet("2").0, packet("[[2]]").0);
+    assert_eq!(packet("2"), packet("[[2]]"));
+    assert!(packet("[[1],[2,3,4]]") < packet("[[1],4]"));
+    assert!(packet("[300]") > packet("[[255]]"));
+    assert_eq!(packet("[1,[2]]").to_string(), "[1,[2]]");
+
+    // Packet order is a total order, so the standard library can use it
+    let mut packets = [packet("[3]"), packet("[]"), packet("[[1],2]"), packet("0")];
+    packets.sort();
+    let sorted: Vec<String> = packets.iter().map(Packet::to_string).collect();
+    assert_eq!(sorted, ["[]", "0", "[[1],2]", "[3]"]);
+    assert_eq!(packets.iter().max(), Some(&packet("[[[3]]]")));
+
+    let error = |s: &str| s.parse::<Item>().unwrap_err();
+    assert_eq!(
+        error("[1,]"),
+        ItemError::Unexpected {
+            column: 4,
+            found: ']'
+        }
+    );
+    assert_eq!(error("[[1]"), ItemError::UnexpectedEnd { column: 5 });
+    assert_eq!(
+        error("[é]"),
+        ItemError::Unexpected {
+            column: 2,
+            found: 'é'
+        }
+    );
+    assert_eq!(
+        error("[[],ü"),
+        ItemError::Unexpected {
+            column: 5,
+            found: 'ü'
+        }
+    );
+    assert_eq!(
+        error("[1]]"),
+        ItemError::Unexpected {
+            column: 4,
+            found: ']'
+        }
+    );
+    assert_eq!(
+        error("[1,18446744073709551616]"),
+        ItemError::Overflow { column: 4 }
+    );
+    assert_eq!(
+        parse("[1]\n[2]\n\n[3]\n[4\n"),
+        Err(ParseError::Item {
+            line: 5,
+            error: ItemError::UnexpectedEnd { column: 3 }
+        })
+    );
+    assert_eq!(
+        parse("[1]\n[2]\n\n[3]\n"),
+        Err(ParseError::BadPair { line: 4 })
+    );
+}
@@ -0,0 +1,259 @@
+//! Solutions to 2022: Advent of Code day 16
+//! By Peter Fornwall
+
+use crate::sniff::Confidence;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+use std::collections::{HashMap, VecDeque};
+
+#[derive(Debug)]
+pub struct Valve {
+    pub flow: i32,
+    pub tunnels: Vec<(usize, usize)>,
+}
+
+pub fn looks_like(input: &str) -> Confidence {
+    input.trim_start().starts_with("Valve ").into()
+}
+
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn parse(input: &str) -> (Vec<Valve>, usize) {
+    let mut m: HashMap<String, usize> = HashMap::new();
+    let mut v: Vec<Valve> = Vec::new();
+
+    for line in input.trim().lines() {
+        let valve = &line[6..8];
+        m.insert(valve.to_string(), m.len());
+    }
+    let mut conv = HashMap::new();
+    let mut conv_ind = 0;
+    for (i, line) in input.trim().lines().enumerate() {
+        let s = line.trim();
+        let valve = &line[6..8];
+        let flow_str = s.split(['=', ';']).nth(1).unwrap();
+        let flow = flow_str.parse::<i32>().unwrap();
+        let tunnels: Vec<_> = s
+            .split(", ")
+            .map(|s| (m[&s[s.len() - 2..].to_string()], 0))
+            .collect();
+
+        v.push(Valve { flow, tunnels });
+        if flow > 0 || valve == "AA" {
+            conv.insert(i, conv_ind);
+            conv_ind += 1;
+        }
+    }
+
+    let mut v2: Vec<Valve> = Vec::new();
+    let start_ind = m["AA"];
+    for (ix, valve) in v.iter().enumerate() {
+        if valve.flow == 0 && ix != start_ind {
+            continue;
+        }
+        // Eliminate flow=0 valves
+        let mut dist: HashMap<usize, i32> = HashMap::new();
+        let mut s = VecDeque::new();
+        s.push_back((ix, 0));
+        while let Some((exp_ix, d)) = s.pop_front() {
+            if dist.contains_key(&exp_ix) && dist[&exp_ix] <= d {
+                continue;
+            }
+            dist.insert(exp_ix, d);
+            for (tunnel, _) in &v[exp_ix].tunnels {
+                s.push_back((*tunnel, d + 1));
+            }
+        }
+
+        let mut tunnels = Vec::new();
+        for (ind, dist) in dist {
+            if ix != ind && (v[ind].flow > 0 || ind == start_ind) {
+                tunnels.push((conv[&ind], dist as usize));
+            }
+        }
+        v2.push(Valve {
+            flow: valve.flow,
+            tunnels,
+        });
+    }
+
+    (v2, conv[&start_ind])
+}
+
+fn search(
+    valves: &Vec<Valve>,
+    valve_idx: usize,
+    open_valves: u64,
+    time_left: i32,
+    seen: &mut HashMap<(usize, u64, i32, bool), i32>,
+    do_second: bool,
+    start_idx: usize,
+) -> i32 {
+    if time_left <= 0 {
+        if do_second {
+            return search(valves, start_idx, open_valves, 26, seen, false, start_idx);
+        } else {
+            return 0;
+        }
+    }
+    if let Some(seen_sum) = seen.get(&(valve_idx, open_valves, time_left, do_second)) {
+        return *seen_sum;
+    }
+
+    let mut ret = 0;
+    if (open_valves & (1u64 << valve_idx)) == 0 && valves[valve_idx].flow > 0 {
+        // Open valve
+        ret = (valves[valve_idx].flow * (time_left - 1)
+            + search(
+                valves,
+                valve_idx,
+                open_valves | (1u64 << valve_idx),
+                time_left - 1,
+                seen,
+                do_second,
+                start_idx,
+            ))
+        .max(ret);
+    }
+    for (go_valve, go_dist) in &valves[valve_idx].tunnels {
+        ret = search(
+            valves,
+            *go_valve,
+            open_valves,
+            time_left - *go_dist as i32,
+            seen,
+            do_second,
+            start_idx,
+        )
+        .max(ret);
+    }
+
+    seen.insert((valve_idx, open_valves, time_left, do_second), ret);
+    ret
+}
+
+/// Tries every order of opening valves, recording the best release for
+/// every set of opened valves.
+fn all_paths(
+    valves: &[Valve],
+    valve_idx: usize,
+    open_valves: u64,
+    time_left: i32,
+    released: i32,
+    best: &mut HashMap<u64, i32>,
+) {
+    let best_released = best.entry(open_valves).or_insert(0);
+    *best_released = (*best_released).max(released);
+    if (open_valves & (1u64 << valve_idx)) == 0 && valves[valve_idx].flow > 0 {
+        // Only the start valve can be closed when standing on it
+        let released = released + valves[valve_idx].flow * (time_left - 1);
+        let open = open_valves | (1u64 << valve_idx);
+        all_paths(valves, valve_idx, open, time_left - 1, released, best);
+    }
+    for (go_valve, go_dist) in &valves[valve_idx].tunnels {
+        let time_left = time_left - *go_dist as i32 - 1;
+        if time_left <= 0 || (open_valves & (1u64 << go_valve)) != 0 || valves[*go_valve].flow == 0
+        {
+            continue;
+        }
+        let released = released + valves[*go_valve].flow * time_left;
+        let open_valves = open_valves | (1u64 << go_valve);
+        all_paths(valves, *go_valve, open_valves, time_left, released, best);
+    }
+}
+
+/// Random small, connected cave system.
+pub fn generate(rng: &mut StdRng) -> String {
+    let nr_valves = rng.gen_range(2..=8);
+    let mut names = vec!["AA".to_string()];
+    while names.len() < nr_valves {
+        let name: String = (0..2).map(|_| rng.gen_range(b'A'..=b'Z') as char).collect();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    let mut tunnels: Vec<Vec<usize>> = vec![Vec::new(); nr_valves];
+    let mut connect = |a: usize, b: usize| {
+        if a != b && !tunnels[a].contains(&b) {
+            tunnels[a].push(b);
+            tunnels[b].push(a);
+        }
+    };
+    for ix in 1..nr_valves {
+        connect(ix, rng.gen_range(0..ix));
+    }
+    for _ in 0..rng.gen_range(0..nr_valves) {
+        connect(rng.gen_range(0..nr_valves), rng.gen_range(0..nr_valves));
+    }
+    names
+        .iter()
+        .enumerate()
+        .map(|(ix, name)| {
+            let flow = if ix == 0 || rng.gen_ratio(1, 3) {
+                0
+            } else {
+                rng.gen_range(1..=25)
+            };
+            let mut to: Vec<_> = tunnels[ix].iter().map(|t| names[*t].as_str()).collect();
+            to.shuffle(rng);
+            if to.len() == 1 {
+                format!(
+                    "Valve {name} has flow rate={flow}; tunnel leads to valve {}",
+                    to[0]
+                )
+            } else {
+                let to = to.join(", ");
+                format!("Valve {name} has flow rate={flow}; tunnels lead to valves {to}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn part1(input: &str) -> i32 {
+    let (valves, start_idx) = parse(input);
+    let mut seen: HashMap<(usize, u64, i32, bool), i32> = HashMap::new();
+    let ret = search(&valves, start_idx, 0u64, 30, &mut seen, false, start_idx);
+    tracing::debug!(nodes = seen.len(), "valve search done");
+    ret
+}
+
+pub fn part2(input: &str) -> i32 {
+    let (valves, start_idx) = parse(input);
+    let mut seen: HashMap<(usize, u64, i32, bool), i32> = HashMap::new();
+    let ret = search(&valves, start_idx, 0u64, 26, &mut seen, true, start_idx);
+    tracing::debug!(nodes = seen.len(), "valve search done");
+    ret
+}
+
+pub fn part1_exhaustive(input: &str) -> i32 {
+    let (valves, start_idx) = parse(input);
+    let mut best = HashMap::new();
+    all_paths(&valves, start_idx, 0u64, 30, 0, &mut best);
+    *best.values().max().unwrap()
+}
+
+pub fn part2_exhaustive(input: &str) -> i32 {
+    let (valves, start_idx) = parse(input);
+    let mut best = HashMap::new();
+    all_paths(&valves, start_idx, 0u64, 26, 0, &mut best);
+    let mut ret = 0;
+    for (open1, released1) in &best {
+        for (open2, released2) in &best {
+            if open1 & open2 == 0 {
+                ret = ret.max(released1 + released2);
+            }
+        }
+    }
+    ret
+}
+
+#[test]
+fn test1() {
+    let input = include_str!("../../inputs/day16_example1.txt");
+
+    let result_p1 = part1(input);
+    println!("Part1: {}", result_p1);
+    assert_eq!(result_p1, 1651);
+    let result_p2 = part2(input);
+    println!("Part2: {}", result_p2);
+    assert_eq!(result_p2, 1707);
+}
@@ -0,0 +1,114 @@
+//! Solutions to 2022: Advent of Code day 20
+//! By Peter Fornwall
+
+use crate::sniff::{self, Confidence};
+use rand::{rngs::StdRng, Rng};
+use std::collections::VecDeque;
+
+pub fn looks_like(input: &str) -> Confidence {
+    if !sniff::all_lines(input, sniff::is_signed) {
+        Confidence::No
+    } else if sniff::sample(input).any(|l| l.starts_with('-')) {
+        Confidence::High
+    } else {
+        Confidence::Low
+    }
+}
+
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn parse(input: &str) -> Vec<i32> {
+    input
+        .trim()
+        .lines()
+        .map(|line| line.trim().parse::<i32>().unwrap())
+        .collect()
+}
+
+pub fn decrypt(v: &[i32], rounds: i32, dec_key: i64) -> i64 {
+    let mut seq: VecDeque<_> = v.iter().map(|x| *x as i64 * dec_key).enumerate().collect();
+
+    for _ in 0..rounds {
+        for test_ix in 0..v.len() {
+            for i in 0..seq.len() {
+                let item = (seq[i].0, seq[i].1);
+                if item.0 == test_ix {
+                    seq.remove(i);
+                    let new_ind = (i as i64 + item.1).rem_euclid(seq.len() as i64);
+                    seq.insert(new_ind as usize, item);
+                    break;
+                }
+            }
+        }
+    }
+
+    let ind = seq.iter().position(|item| item.1 == 0).unwrap();
+    (1..=3).map(|x| seq[(x * 1000 + ind) % seq.len()].1).sum()
+}
+
+/// Reference for `decrypt`: moves every number one position at a time,
+/// exactly as the puzzle describes the mixing.
+pub fn decrypt_reference(v: &[i32], rounds: i32, dec_key: i64) -> i64 {
+    let mut seq: Vec<_> = v.iter().map(|x| *x as i64 * dec_key).enumerate().collect();
+    let len = seq.len();
+
+    for _ in 0..rounds {
+        for test_ix in 0..len {
+            let mut i = seq.iter().position(|item| item.0 == test_ix).unwrap();
+            // Moving len - 1 steps around the circle gives back the same order
+            let steps = seq[i].1.rem_euclid(len as i64 - 1);
+            for _ in 0..steps {
+                let next = (i + 1) % len;
+                seq.swap(i, next);
+                i = next;
+            }
+        }
+    }
+
+    let ind = seq.iter().position(|item| item.1 == 0).unwrap();
+    (1..=3).map(|x| seq[(x * 1000 + ind) % len].1).sum()
+}
+
+/// Random small encrypted file with a single zero.
+pub fn generate(rng: &mut StdRng) -> String {
+    let len = rng.gen_range(2..=12);
+    let mut v: Vec<i32> = (1..len)
+        .map(|_| rng.gen_range(1..=15) * if rng.gen() { 1 } else { -1 })
+        .collect();
+    v.insert(rng.gen_range(0..len), 0);
+    v.iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn part1(input: &str) -> i64 {
+    let v = parse(input);
+    decrypt(&v, 1, 1)
+}
+
+pub fn part2(input: &str) -> i64 {
+    let v = parse(input);
+    decrypt(&v, 10, 811589153)
+}
+
+pub fn part1_reference(input: &str) -> i64 {
+    let v = parse(input);
+    decrypt_reference(&v, 1, 1)
+}
+
+pub fn part2_reference(input: &str) -> i64 {
+    let v = parse(input);
+    decrypt_reference(&v, 10, 811589153)
+}
+
+#[test]
+fn test1() {
+    let input = include_str!("../../inputs/day20_example1.txt");
+
+    let result_p1 = part1(input);
+    println!("Part1: {}", result_p1);
+    assert_eq!(result_p1, 3);
+    let result_p2 = part2(input);
+    println!("Part2: {}", result_p2);
+    assert_eq!(result_p2, 1623178306);
+}
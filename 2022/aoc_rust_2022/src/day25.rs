@@ -0,0 +1,49 @@
+//! Solutions to 2022: Advent of Code day 25
+//! By Peter Fornwall
+
+use crate::sniff::{self, Confidence};
+
+pub fn looks_like(input: &str) -> Confidence {
+    if !sniff::all_lines(input, |l| {
+        !l.is_empty() && l.bytes().all(|c| b"=-012".contains(&c))
+    }) {
+        Confidence::No
+    } else if sniff::sample(input).any(|l| l.contains(['=', '-'])) {
+        Confidence::High
+    } else {
+        Confidence::Low
+    }
+}
+
+fn snafu_to_nr(s: &str) -> i64 {
+    let mut base: i64 = 1;
+    let mut nr = 0;
+    for c in s.chars().rev() {
+        let x = "=-012".find(c).unwrap() as i64 - 2;
+        nr += base * x;
+        base *= 5;
+    }
+    nr
+}
+
+pub fn part1(input: &str) -> String {
+    let mut sum: i64 = input.trim().lines().map(snafu_to_nr).sum();
+
+    let mut s = String::new();
+    while sum != 0 {
+        let rem = (sum + 2) % 5;
+        sum = (sum + 2) / 5;
+        s.push("=-012".chars().nth(rem as usize).unwrap());
+    }
+    let snaf = s.chars().rev().collect::<String>();
+    snaf
+}
+
+#[test]
+fn test1() {
+    let input = include_str!("../../inputs/day25_example1.txt");
+
+    let result_p1 = part1(input);
+    println!("Part1: {}", result_p1);
+    assert_eq!(result_p1, "2=-1=0");
+}
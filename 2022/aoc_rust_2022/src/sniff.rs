@@ -0,0 +1,52 @@
+//! Cheap input format checks, used to detect inputs given to the wrong day
+//! By Peter Fornwall
+
+use itertools::Itertools;
+
+/// Number of lines looked at when checking the format of an input.
+pub const SAMPLE_LINES: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Confidence {
+    No,
+    Low,
+    High,
+}
+
+impl From<bool> for Confidence {
+    fn from(b: bool) -> Self {
+        if b {
+            Confidence::High
+        } else {
+            Confidence::No
+        }
+    }
+}
+
+/// First lines of the input (leading and trailing newlines removed).
+pub fn sample(input: &str) -> impl Iterator<Item = &str> {
+    input
+        .trim_matches(['\n', '\r'])
+        .lines()
+        .take(SAMPLE_LINES)
+        .map(|line| line.trim_end_matches('\r'))
+}
+
+/// True if the input is non-empty and `check` holds for every sampled line.
+pub fn all_lines(input: &str, check: impl Fn(&str) -> bool) -> bool {
+    let mut lines = sample(input).peekable();
+    lines.peek().is_some() && lines.all(check)
+}
+
+/// True if all sampled lines have the same length.
+pub fn same_width(input: &str) -> bool {
+    sample(input).map(str::len).all_equal()
+}
+
+pub fn is_digits(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|c| c.is_ascii_digit())
+}
+
+pub fn is_signed(s: &str) -> bool {
+    is_digits(s.strip_prefix('-').unwrap_or(s))
+}
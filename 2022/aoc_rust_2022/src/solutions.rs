@@ -0,0 +1,435 @@
+//! Registry of all 2022 solutions, used by the `aoc` runner
+//! By Peter Fornwall
+
+use std::{panic, path::PathBuf};
+
+use rand::rngs::StdRng;
+
+use crate::{sniff::Confidence, *};
+
+pub type Solver = fn(&str) -> String;
+
+/// One named way of solving a part.
+pub struct Impl {
+    pub name: &'static str,
+    pub solve: Solver,
+}
+
+pub struct Day {
+    pub day: u8,
+    pub looks_like: fn(&str) -> Confidence,
+    /// Implementations of each part, the first one is used when running the day
+    pub part1: Vec<Impl>,
+    pub part2: Vec<Impl>,
+    /// Random input generator, used when cross-checking implementations
+    pub generate: Option<fn(&mut StdRng) -> String>,
+}
+
+/// Answers from all implementations of a part, when they don't agree or
+/// one of them panicked. A panic is kept as `Err` with its message.
+#[derive(Debug)]
+pub struct Mismatch {
+    pub part: u8,
+    pub answers: Vec<(&'static str, Result<String, String>)>,
+}
+
+fn single(solve: Solver) -> Vec<Impl> {
+    vec![Impl {
+        name: "default",
+        solve,
+    }]
+}
+
+/// Answer of a search that may come up empty.
+fn found(answer: Option<usize>) -> String {
+    answer.map_or("none".to_string(), |a| a.to_string())
+}
+
+pub fn days() -> Vec<Day> {
+    vec![
+        Day {
+            day: 1,
+            looks_like: day01::looks_like,
+            part1: single(|s| day01::part1(s).to_string()),
+            part2: single(|s| day01::part2(s).to_string()),
+            generate: None,
+        },
+        Day {
+            day: 2,
+            looks_like: day02::looks_like,
+            part1: single(|s| day02::part1(&day02::parse(s)).to_string()),
+            part2: single(|s| day02::part2(&day02::parse(s)).to_string()),
+            generate: None,
+        },
+        Day {
+            day: 3,
+            looks_like: day03::looks_like,
+            part1: vec![
+                Impl {
+                    name: "bitmask",
+                    solve: |s| day03::part1(&day03::parse(s).unwrap()).to_string(),
+                },
+                Impl {
+                    name: "hashset",
+                    solve: |s| day03::part1_hashset(&day03::parse(s).unwrap()).to_string(),
+                },
+            ],
+            part2: vec![
+                Impl {
+                    name: "bitmask",
+                    solve: |s| day03::part2(&day03::parse(s).unwrap()).to_string(),
+                },
+                Impl {
+                    name: "hashset",
+                    solve: |s| day03::part2_hashset(&day03::parse(s).unwrap()).to_string(),
+                },
+            ],
+            generate: None,
+        },
+        Day {
+            day: 4,
+            looks_like: day04::looks_like,
+            part1: single(|s| day04::part1(&day04::parse(s)).to_string()),
+            part2: single(|s| day04::part2(&day04::parse(s)).to_string()),
+            generate: None,
+        },
+        Day {
+            day: 5,
+            looks_like: day05::looks_like,
+            part1: single(day05::part1),
+            part2: single(day05::part2),
+            generate: None,
+        },
+        Day {
+            day: 6,
+            looks_like: day06::looks_like,
+            part1: vec![
+                Impl {
+                    name: "sliding",
+                    solve: |s| found(day06::part1(s)),
+                },
+                Impl {
+                    name: "reference",
+                    solve: |s| found(day06::find_unique_reference(s, 4)),
+                },
+            ],
+            part2: vec![
+                Impl {
+                    name: "sliding",
+                    solve: |s| found(day06::part2(s)),
+                },
+                Impl {
+                    name: "reference",
+                    solve: |s| found(day06::find_unique_reference(s, 14)),
+                },
+            ],
+            generate: Some(day06::generate),
+        },
+        Day {
+            day: 7,
+            looks_like: day07::looks_like,
+            part1: single(|s| day07::part1(s).to_string()),
+            part2: single(|s| day07::part2(s).to_string()),
+            generate: None,
+        },
+        Day {
+            day: 8,
+            looks_like: day08::looks_like,
+            part1: vec![
+                Impl {
+                    name: "stack",
+                    solve: |s| day08::part1(&day08::parse(s)).to_string(),
+                },
+                Impl {
+                    name: "reference",
+                    solve: |s| day08::part1_reference(&day08::parse(s)).to_string(),
+                },
+            ],
+            part2: vec![
+                Impl {
+                    name: "stack",
+                    solve: |s| day08::part2(&day08::parse(s)).to_string(),
+                },
+                Impl {
+                    name: "reference",
+                    solve: |s| day08::part2_reference(&day08::parse(s)).to_string(),
+                },
+            ],
+            generate: Some(day08::generate),
+        },
+        Day {
+            day: 9,
+            looks_like: day09::looks_like,
+            part1: single(|s| day09::part1(s).to_string()),
+            part2: single(|s| day09::part2(s).to_string()),
+            generate: None,
+        },
+        Day {
+            day: 10,
+            looks_like: day10::looks_like,
+            part1: single(|s| day10::part1(s).to_string()),
+            part2: single(day10::part2),
+            generate: None,
+        },
+        Day {
+            day: 11,
+            looks_like: day11::looks_like,
+            part1: single(|s| day11::part1(&day11::parse(s).unwrap()).to_string()),
+            part2: single(|s| day11::part2(&day11::parse(s).unwrap()).to_string()),
+            generate: None,
+        },
+        Day {
+            day: 12,
+            looks_like: day12::looks_like,
+            part1: single(|s| day12::part1(s).to_string()),
+            part2: single(|s| day12::part2(s).to_string()),
+            generate: None,
+        },
+        Day {
+            day: 13,
+            looks_like: day13::looks_like,
+            part1: single(|s| day13::part1(s).to_string()),
+            part2: single(|s| day13::part2(s).to_string()),
+            generate: None,
+        },
+        Day {
+            day: 14,
+            looks_like: day14::looks_like,
+            part1: single(|s| day14::part1(s).to_string()),
+            part2: single(|s| day14::part2(s).to_string()),
+            generate: None,
+        },
+        Day {
+            day: 15,
+            looks_like: day15::looks_like,
+            part1: single(|s| day15::part1(s, 2_000_000).to_string()),
+            part2: single(|s| day15::part2(s, 4_000_000).to_string()),
+            generate: None,
+        },
+        Day {
+            day: 16,
+            looks_like: day16::looks_like,
+            part1: vec![
+                Impl {
+                    name: "memoized",
+                    solve: |s| day16::part1(s).to_string(),
+                },
+                Impl {
+                    name: "exhaustive",
+                    solve: |s| day16::part1_exhaustive(s).to_string(),
+                },
+            ],
+            part2: vec![
+                Impl {
+                    name: "memoized",
+                    solve: |s| day16::part2(s).to_string(),
+                },
+                Impl {
+                    name: "exhaustive",
+                    solve: |s| day16::part2_exhaustive(s).to_string(),
+                },
+            ],
+            generate: Some(day16::generate),
+        },
+        Day {
+            day: 17,
+            looks_like: day17::looks_like,
+            part1: single(|s| day17::part1(s).to_string()),
+            part2: single(|s| day17::part2(s).to_string()),
+            generate: None,
+        },
+        Day {
+            day: 18,
+            looks_like: day18::looks_like,
+            part1: single(|s| day18::part1(s).to_string()),
+            part2: single(|s| day18::part2(s).to_string()),
+            generate: None,
+        },
+        Day {
+            day: 19,
+            looks_like: day19::looks_like,
+            part1: single(|s| day19::part1(s).to_string()),
+            part2: single(|s| day19::part2(s).to_string()),
+            generate: None,
+        },
+        Day {
+            day: 20,
+            looks_like: day20::looks_like,
+            part1: vec![
+                Impl {
+                    name: "vecdeque",
+                    solve: |s| day20::part1(s).to_string(),
+                },
+                Impl {
+                    name: "reference",
+                    solve: |s| day20::part1_reference(s).to_string(),
+                },
+            ],
+            part2: vec![
+                Impl {
+                    name: "vecdeque",
+                    solve: |s| day20::part2(s).to_string(),
+                },
+                Impl {
+                    name: "reference",
+                    solve: |s| day20::part2_reference(s).to_string(),
+                },
+            ],
+            generate: Some(day20::generate),
+        },
+        Day {
+            day: 21,
+            looks_like: day21::looks_like,
+            part1: single(|s| day21::part1(s).to_string()),
+            part2: single(|s| day21::part2(s).to_string()),
+            generate: None,
+        },
+        Day {
+            day: 22,
+            looks_like: day22::looks_like,
+            part1: single(|s| day22::part1(s).to_string()),
+            part2: single(|s| day22::part2(s).to_string()),
+            generate: None,
+        },
+        Day {
+            day: 23,
+            looks_like: day23::looks_like,
+            part1: single(|s| day23::part1(s).to_string()),
+            part2: single(|s| day23::part2(s).to_string()),
+            generate: None,
+        },
+        Day {
+            day: 24,
+            looks_like: day24::looks_like,
+            part1: single(|s| day24::part1(s).to_string()),
+            part2: single(|s| day24::part2(s).to_string()),
+            generate: None,
+        },
+        Day {
+            day: 25,
+            looks_like: day25::looks_like,
+            part1: single(day25::part1),
+            part2: Vec::new(),
+            generate: None,
+        },
+    ]
+}
+
+pub fn find(day: u8) -> Option<Day> {
+    days().into_iter().find(|d| d.day == day)
+}
+
+/// Runs a solver, catching a panic as its message.
+fn solve_caught(solve: Solver, input: &str) -> Result<String, String> {
+    panic::catch_unwind(|| solve(input)).map_err(|payload| {
+        payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string())
+    })
+}
+
+/// Runs all implementations of both parts on the same input.
+pub fn crosscheck(day: &Day, input: &str) -> Vec<Mismatch> {
+    let mut mismatches = Vec::new();
+    for (part, impls) in (1..).zip([&day.part1, &day.part2]) {
+        let answers: Vec<_> = impls
+            .iter()
+            .map(|i| (i.name, solve_caught(i.solve, input)))
+            .collect();
+        if answers
+            .iter()
+            .any(|(_, answer)| answer.is_err() || *answer != answers[0].1)
+        {
+            mismatches.push(Mismatch { part, answers });
+        }
+    }
+    mismatches
+}
+
+/// Days whose input format matches the input, most likely first.
+pub fn identify(input: &str) -> Vec<(u8, Confidence)> {
+    let mut matches: Vec<_> = days()
+        .iter()
+        .map(|d| (d.day, (d.looks_like)(input)))
+        .filter(|(_, confidence)| *confidence > Confidence::No)
+        .collect();
+    matches.sort_by_key(|(_, confidence)| std::cmp::Reverse(*confidence));
+    matches
+}
+
+/// Path to the checked in puzzle input for a day.
+pub fn default_input(day: u8) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../inputs")
+        .join(format!("day{day:02}_input.txt"))
+}
+
+#[test]
+fn test_registry() {
+    let days = days();
+    assert_eq!(days.len(), 25);
+    assert!(days.iter().zip(1..).all(|(d, nr)| d.day == nr));
+    assert_eq!((find(1).unwrap().part1[0].solve)("1\n2\n\n4"), "4");
+}
+
+#[test]
+fn test_crosscheck() {
+    use rand::SeedableRng;
+
+    for day in days().iter().filter(|d| d.generate.is_some()) {
+        for seed in 0..50 {
+            let input = (day.generate.unwrap())(&mut StdRng::seed_from_u64(seed));
+            let mismatches = crosscheck(day, &input);
+            assert!(mismatches.is_empty(), "{input}\n{mismatches:?}");
+        }
+    }
+
+    let panicky = Day {
+        day: 0,
+        looks_like: |_| Confidence::No,
+        part1: vec![
+            Impl {
+                name: "fine",
+                solve: |s| s.len().to_string(),
+            },
+            Impl {
+                name: "panics",
+                solve: |s| panic!("bad input {s}"),
+            },
+        ],
+        part2: single(|_| panic!("always")),
+        generate: None,
+    };
+    let mismatches = crosscheck(&panicky, "x");
+    assert_eq!(mismatches.len(), 2);
+    assert_eq!(
+        mismatches[0].answers,
+        [
+            ("fine", Ok("1".to_string())),
+            ("panics", Err("bad input x".to_string()))
+        ]
+    );
+    assert_eq!(
+        mismatches[1].answers,
+        [("default", Err("always".to_string()))]
+    );
+}
+
+#[test]
+fn test_identify() {
+    let dir = default_input(1).parent().unwrap().to_path_buf();
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        let name = path.file_name().unwrap().to_str().unwrap();
+        let day = name[3..5].parse::<u8>().unwrap();
+        let input = std::fs::read_to_string(&path).unwrap();
+        let matches = identify(&input);
+        assert_eq!(matches[0].0, day, "{name}: {matches:?}");
+        assert!(
+            matches.len() == 1 || matches[1].1 < matches[0].1,
+            "{name}: {matches:?}"
+        );
+    }
+}